@@ -0,0 +1,344 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parenthesized, spanned::Spanned, Attribute, Data, DeriveInput, Ident, Index, Member, Path, Type,
+};
+
+/// the extractor deriving
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Extractor {
+    /// `FromRequestParts`, all fields extracted from the request parts
+    Parts,
+    /// `FromRequest`, the field marked `body` extracted from the whole request at last
+    Request,
+}
+
+/// a field of the composite extractor
+struct ExtractField {
+    member: Member,
+    var: Ident,
+    ty: Type,
+    /// extract by `MapReject<Via<T>, E>` and take the inner value
+    via: Option<Path>,
+    /// extract from the whole request
+    body: bool,
+}
+
+/// read the `rejection(E)` arg of the container attribute `resp_result`
+fn rejection(input: &DeriveInput) -> syn::Result<Type> {
+    let mut rejection = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("resp_result"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rejection") {
+                let content;
+                parenthesized!(content in meta.input);
+                rejection = Some(content.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown arg, expect `rejection(E)`"))
+            }
+        })?;
+    }
+    rejection.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "the rejection type is required, e.g. `#[resp_result(rejection(MyError))]`",
+        )
+    })
+}
+
+/// read the `via(Extractor)` and `body` args of the field attribute `resp_result`
+fn field_args(attrs: &[Attribute]) -> syn::Result<(Option<Path>, bool)> {
+    let (mut via, mut body) = (None, false);
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("resp_result"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("via") {
+                let content;
+                parenthesized!(content in meta.input);
+                via = Some(content.parse::<Path>()?);
+                Ok(())
+            } else if meta.path.is_ident("body") {
+                body = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown arg, expect `via(Extractor)` or `body`"))
+            }
+        })?;
+    }
+    Ok((via, body))
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<ExtractField>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "Only Support Struct"));
+    };
+    data.fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let (via, body) = field_args(&field.attrs)?;
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(idx)),
+            };
+            Ok(ExtractField {
+                member,
+                var: format_ident!("__field_{idx}"),
+                ty: field.ty.clone(),
+                via,
+                body,
+            })
+        })
+        .collect()
+}
+
+impl ExtractField {
+    /// the type actually extracted
+    fn extractor(&self, rejection: &Type) -> TokenStream {
+        let ty = &self.ty;
+        match &self.via {
+            Some(via) => quote!(::axum_resp_result::MapReject<#via<#ty>, #rejection>),
+            None => quote!(#ty),
+        }
+    }
+
+    /// the trait used for extracting, without generic args
+    fn extract_trait(&self) -> TokenStream {
+        if self.body {
+            quote!(::axum_resp_result::__private::axum::extract::FromRequest)
+        } else {
+            quote!(::axum_resp_result::__private::axum::extract::FromRequestParts)
+        }
+    }
+
+    fn bound(&self, rejection: &Type) -> TokenStream {
+        let extractor = self.extractor(rejection);
+        let extract_trait = self.extract_trait();
+        match self.via {
+            Some(_) => quote! {
+                #extractor: #extract_trait<
+                    __S,
+                    Rejection = ::axum_resp_result::RespResult<::axum_resp_result::Nil, #rejection>,
+                >
+            },
+            None => quote! {
+                #extractor: #extract_trait<__S>,
+                #rejection: ::core::convert::From<<#extractor as #extract_trait<__S>>::Rejection>
+            },
+        }
+    }
+
+    /// extract the field into `Option`, record the rejection on failure
+    fn extract(&self, rejection: &Type) -> TokenStream {
+        let var = &self.var;
+        let extractor = self.extractor(rejection);
+        let extract_trait = self.extract_trait();
+        let call = if self.body {
+            quote!(<#extractor as #extract_trait<__S>>::from_request(req, state).await)
+        } else {
+            quote!(<#extractor as #extract_trait<__S>>::from_request_parts(parts, state).await)
+        };
+        let (ok, err) = match self.via {
+            Some(_) => (
+                quote!(__value.0),
+                quote!(::axum_resp_result::__private::take_rejection(__err)),
+            ),
+            None => (
+                quote!(__value),
+                quote!(<#rejection as ::core::convert::From<_>>::from(__err)),
+            ),
+        };
+        quote! {
+            let #var = match #call {
+                ::core::result::Result::Ok(__value) => ::core::option::Option::Some(#ok),
+                ::core::result::Result::Err(__err) => {
+                    ::axum_resp_result::__private::merge_rejection(&mut __rejection, #err);
+                    ::core::option::Option::None
+                }
+            };
+        }
+    }
+}
+
+pub fn gen_from_request_derive(input: &DeriveInput, kind: Extractor) -> syn::Result<TokenStream> {
+    let rejection = rejection(input)?;
+    let fields = fields(input)?;
+
+    let mut bodies = fields.iter().filter(|field| field.body);
+    let body = match (kind, bodies.next(), bodies.next()) {
+        (Extractor::Parts, Some(body), _) => {
+            return Err(syn::Error::new(
+                body.ty.span(),
+                "`body` field only support in `RespFromRequest`",
+            ))
+        }
+        (Extractor::Request, None, _) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`RespFromRequest` need a field marked `#[resp_result(body)]`, \
+                 or using `RespFromRequestParts` instead",
+            ))
+        }
+        (Extractor::Request, Some(_), Some(dup)) => {
+            return Err(syn::Error::new(dup.ty.span(), "duplicate `body` field"))
+        }
+        (_, body, _) => body,
+    };
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::parse_quote!(__S));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|clause| &clause.predicates);
+    let bounds = fields.iter().map(|field| field.bound(&rejection));
+
+    let extract_parts = fields
+        .iter()
+        .filter(|field| !field.body)
+        .map(|field| field.extract(&rejection));
+    let extract_body = body.map(|field| field.extract(&rejection));
+    let vars = fields.iter().map(|field| &field.var);
+    let members = fields.iter().map(|field| &field.member);
+
+    let construct = quote! {
+        if let ::core::option::Option::Some(__err) = __rejection {
+            return ::core::result::Result::Err(::axum_resp_result::RespResult::Err(__err));
+        }
+        ::core::result::Result::Ok(Self {
+            #(#members: #vars.expect("the field extracted without rejection"),)*
+        })
+    };
+
+    let (extract_trait, signature, prepare, restore) = match kind {
+        Extractor::Parts => (
+            quote!(::axum_resp_result::__private::axum::extract::FromRequestParts<__S>),
+            quote! {
+                async fn from_request_parts(
+                    parts: &mut ::axum_resp_result::__private::axum::http::request::Parts,
+                    state: &__S,
+                ) -> ::core::result::Result<Self, Self::Rejection>
+            },
+            quote!(),
+            quote!(),
+        ),
+        Extractor::Request => (
+            quote!(::axum_resp_result::__private::axum::extract::FromRequest<__S>),
+            quote! {
+                async fn from_request(
+                    req: ::axum_resp_result::__private::axum::extract::Request,
+                    state: &__S,
+                ) -> ::core::result::Result<Self, Self::Rejection>
+            },
+            quote!(
+                let (mut __parts, __body) = req.into_parts();
+                let parts = &mut __parts;
+            ),
+            quote!(
+                let req = ::axum_resp_result::__private::axum::extract::Request::from_parts(
+                    __parts, __body,
+                );
+            ),
+        ),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #extract_trait for #ident #ty_generics
+        where
+            __S: ::core::marker::Send + ::core::marker::Sync,
+            #rejection: ::axum_resp_result::RespError
+                + ::axum_resp_result::MergeRejection
+                + ::core::marker::Send,
+            #(#bounds,)*
+            #predicates
+        {
+            type Rejection = ::axum_resp_result::RespResult<::axum_resp_result::Nil, #rejection>;
+
+            #signature {
+                let mut __rejection = ::core::option::Option::<#rejection>::None;
+                #prepare
+                #(#extract_parts)*
+                #restore
+                #extract_body
+                #construct
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use syn::DeriveInput;
+
+    use super::{gen_from_request_derive, Extractor};
+
+    #[test]
+    fn test_derive() {
+        const CODE: &str = r#"
+            #[resp_result(rejection(PlainError))]
+            struct UserQuery<T> {
+                #[resp_result(via(Path))]
+                id: u32,
+                #[resp_result(via(Query))]
+                page: T,
+                auth: AuthUser,
+            }
+        "#;
+        let input = syn::parse_str::<DeriveInput>(CODE).unwrap();
+        let code = gen_from_request_derive(&input, Extractor::Parts)
+            .unwrap()
+            .to_string();
+        assert!(code.contains("MapReject < Path < u32 > , PlainError >"));
+        assert!(code.contains("MapReject < Query < T > , PlainError >"));
+        assert!(code.contains("PlainError : :: core :: convert :: From << AuthUser as"));
+        assert!(code.contains("page : __field_1 . expect"));
+
+        // `body` field is required by `FromRequest`
+        assert!(gen_from_request_derive(&input, Extractor::Request).is_err());
+    }
+
+    #[test]
+    fn test_derive_body() {
+        const CODE: &str = r#"
+            #[resp_result(rejection(PlainError))]
+            struct CreateUser(
+                #[resp_result(body, via(Json))] User,
+                #[resp_result(via(Path))] u32,
+            );
+        "#;
+        let input = syn::parse_str::<DeriveInput>(CODE).unwrap();
+        let code = gen_from_request_derive(&input, Extractor::Request)
+            .unwrap()
+            .to_string();
+        // the body field extracted after the parts restored
+        let restore = code.find("Request :: from_parts").unwrap();
+        let body = code
+            .find("MapReject < Json < User > , PlainError > as")
+            .unwrap();
+        assert!(restore < body);
+        assert!(gen_from_request_derive(&input, Extractor::Parts).is_err());
+    }
+
+    #[test]
+    fn test_derive_invalid() {
+        for code in [
+            "struct A { id: u32 }",
+            "#[resp_result(reject(E))] struct A { id: u32 }",
+            "#[resp_result(rejection(E))] struct A { #[resp_result(from(Path))] id: u32 }",
+            "#[resp_result(rejection(E))] enum A { B(u8) }",
+        ] {
+            let input = syn::parse_str::<DeriveInput>(code).unwrap();
+            assert!(
+                gen_from_request_derive(&input, Extractor::Parts).is_err(),
+                "{code}"
+            );
+        }
+    }
+}
@@ -6,11 +6,18 @@ use syn::Expr;
 pub struct RespErrorCodeGen {
     pub(crate) ident: syn::Ident,
     pub(crate) variants: Vec<VariantCodeGen>,
+    pub(crate) dyn_error: bool,
+    pub(crate) merge_first: bool,
 }
 
 impl ToTokens for RespErrorCodeGen {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let RespErrorCodeGen { ident, variants } = self;
+        let RespErrorCodeGen {
+            ident,
+            variants,
+            dyn_error,
+            merge_first,
+        } = self;
         let resp_msg_rows = variants
             .iter()
             .filter_map(
@@ -29,6 +36,17 @@ impl ToTokens for RespErrorCodeGen {
             )
             .map(|(ident, code)| quote!(Self::#ident{..} => #code));
 
+        let business_code_rows = variants
+            .iter()
+            .filter_map(
+                |VariantCodeGen {
+                     ident,
+                     business_code,
+                     ..
+                 }| { Some((ident, (*business_code)?)) },
+            )
+            .map(|(ident, code)| quote!(Self::#ident{..} => #code));
+
         let token = quote! {
             impl ::axum_resp_result::RespError for #ident{
                 fn log_message(&self) -> std::borrow::Cow<'_, str> {
@@ -40,6 +58,12 @@ impl ToTokens for RespErrorCodeGen {
                         _=> ::axum_resp_result::StatusCode::INTERNAL_SERVER_ERROR
                     }
                 }
+                fn business_code(&self) -> i64 {
+                    match self {
+                        #(#business_code_rows,)*
+                        _ => <Self as ::axum_resp_result::RespError>::http_code(self).as_u16().into()
+                    }
+                }
                 fn resp_message(&self) -> std::borrow::Cow<'_, str> {
                     match self{
                         #(#resp_msg_rows,)*
@@ -48,7 +72,23 @@ impl ToTokens for RespErrorCodeGen {
                 }
             }
         };
-        tokens.extend(token)
+        tokens.extend(token);
+
+        if *dyn_error {
+            tokens.extend(quote! {
+                impl ::core::convert::From<#ident> for ::axum_resp_result::DynRespError {
+                    fn from(err: #ident) -> Self {
+                        ::axum_resp_result::DynRespError::new(err)
+                    }
+                }
+            })
+        }
+
+        if *merge_first {
+            tokens.extend(quote! {
+                impl ::axum_resp_result::MergeRejection for #ident {}
+            })
+        }
     }
 }
 
@@ -56,4 +96,5 @@ pub struct VariantCodeGen {
     pub(crate) ident: syn::Ident,
     pub(crate) resp_msg: Option<String>,
     pub(crate) http_code: Option<Expr>,
+    pub(crate) business_code: Option<i64>,
 }
@@ -10,6 +10,12 @@ use darling::{ast, FromDeriveInput};
 pub struct RespErrorDeriveInput {
     pub(crate) ident: syn::Ident,
     pub(crate) data: ast::Data<VariantInfo, Ignored>,
+    /// also impl `From<Self>` for `DynRespError`
+    #[darling(default)]
+    pub(crate) dyn_error: bool,
+    /// also impl `MergeRejection` keeping the first rejection
+    #[darling(default)]
+    pub(crate) merge_first: bool,
 }
 
 impl TryInto<RespErrorCodeGen> for RespErrorDeriveInput {
@@ -27,6 +33,7 @@ impl TryInto<RespErrorCodeGen> for RespErrorDeriveInput {
             ident,
             http_code,
             resp_msg,
+            business_code,
         } in variants
         {
             let http_code = http_code.map(TryInto::try_into).transpose()?;
@@ -34,12 +41,15 @@ impl TryInto<RespErrorCodeGen> for RespErrorDeriveInput {
                 ident,
                 resp_msg,
                 http_code,
+                business_code,
             })
         }
 
         Ok(RespErrorCodeGen {
             ident: self.ident,
             variants: vars,
+            dyn_error: self.dyn_error,
+            merge_first: self.merge_first,
         })
     }
 }
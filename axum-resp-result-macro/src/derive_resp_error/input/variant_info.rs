@@ -9,4 +9,6 @@ pub struct VariantInfo {
     pub(crate) resp_msg: Option<String>,
     #[darling(rename = "err_code")]
     pub(crate) http_code: Option<HttpCode>,
+    #[darling(rename = "biz_code")]
+    pub(crate) business_code: Option<i64>,
 }
@@ -8,7 +8,7 @@ use syn::DeriveInput;
 
 mod codegen;
 mod input;
-mod structure;
+pub(crate) mod structure;
 
 pub fn gen_resp_error_derive(input: &DeriveInput) -> syn::Result<TokenStream> {
     if !input.generics.params.is_empty() {
@@ -1 +1 @@
-pub(super) mod http_code;
+pub(crate) mod http_code;
@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Field, Fields, Index, Member};
+
+/// find the inner field
+/// - the tuple struct with only one field
+/// - the named struct with only one field or only one field marked `#[inner]`
+fn inner_field(input: &DeriveInput) -> syn::Result<(Member, &Field)> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "Only Support Struct"));
+    };
+
+    match &data.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            Ok((Member::Unnamed(Index::from(0)), &fields.unnamed[0]))
+        }
+        Fields::Unnamed(fields) => Err(syn::Error::new(
+            fields.span(),
+            "tuple struct should have exactly one field",
+        )),
+        Fields::Named(fields) => {
+            let mut marked = fields
+                .named
+                .iter()
+                .filter(|field| field.attrs.iter().any(|attr| attr.path().is_ident("inner")));
+            let field = match (marked.next(), marked.next()) {
+                (Some(field), None) => field,
+                (Some(_), Some(dup)) => {
+                    return Err(syn::Error::new(dup.span(), "duplicate `#[inner]` field"))
+                }
+                (None, _) if fields.named.len() == 1 => &fields.named[0],
+                (None, _) => {
+                    return Err(syn::Error::new(
+                        fields.span(),
+                        "mark the inner field with `#[inner]`",
+                    ))
+                }
+            };
+            let ident = field.ident.clone().expect("named field without ident");
+            Ok((Member::Named(ident), field))
+        }
+        Fields::Unit => Err(syn::Error::new(
+            input.span(),
+            "unit struct has no inner field",
+        )),
+    }
+}
+
+pub fn gen_to_inner_derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let (member, field) = inner_field(input)?;
+    let ident = &input.ident;
+    let ty = &field.ty;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::axum_resp_result::ToInner for #ident #ty_generics #where_clause {
+            type Inner = #ty;
+
+            fn to_inner(self) -> Self::Inner {
+                self.#member
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use syn::DeriveInput;
+
+    use super::gen_to_inner_derive;
+
+    #[test]
+    fn test_derive() {
+        let tuple = syn::parse_str::<DeriveInput>("struct AuthUser<U: Clone>(pub U);").unwrap();
+        let code = gen_to_inner_derive(&tuple).unwrap().to_string();
+        assert!(code.contains("type Inner = U"));
+        assert!(code.contains("self . 0"));
+
+        const NAMED: &str = r#"
+            struct Tenant {
+                #[inner]
+                id: TenantId,
+                name: String,
+            }
+        "#;
+        let named = syn::parse_str::<DeriveInput>(NAMED).unwrap();
+        let code = gen_to_inner_derive(&named).unwrap().to_string();
+        assert!(code.contains("type Inner = TenantId"));
+        assert!(code.contains("self . id"));
+    }
+
+    #[test]
+    fn test_derive_invalid() {
+        for code in [
+            "struct A(u8, u8);",
+            "struct A { a: u8, b: u8 }",
+            "struct A { #[inner] a: u8, #[inner] b: u8 }",
+            "struct A;",
+            "enum A { B(u8) }",
+        ] {
+            let input = syn::parse_str::<DeriveInput>(code).unwrap();
+            assert!(gen_to_inner_derive(&input).is_err(), "{code}");
+        }
+    }
+}
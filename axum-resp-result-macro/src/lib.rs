@@ -1,18 +1,42 @@
+mod derive_from_request;
 mod derive_resp_error;
+mod derive_to_inner;
 mod proc_resp_result;
-use proc_resp_result::Function;
-use quote::quote;
+use proc_resp_result::{AttrArgs, RespResultItem};
 use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
 
+use crate::derive_from_request::{gen_from_request_derive, Extractor};
 use crate::derive_resp_error::gen_resp_error_derive;
+use crate::derive_to_inner::gen_to_inner_derive;
 
 /// convert a return [Result] [`Handler`](axum::Handler) return [`RespResult`]
+///
+/// ## Args
+/// - `status = 201` or `status = "Created"`: overwrite the status code on success
+/// - `header("location" = expr, ...)`: insert the headers on success
+/// - `empty_body`: empty the response body on success
+/// - `not_found`: convert `Ok(None)` of `Result<Option<T>, E>` into `NotFoundError`, require `E: From<NotFoundError>`,
+///   only on `fn`
+/// - `skip`: keep the function unchanged
+/// - `instrument(level = "info", err_level = "error", skip(arg, ...), skip_all)`: open a tracing span
+///   for the handler and record the outcome, require feature `trace`
+///
+/// with any of the args except `skip` and `instrument`, the handler will return `RespResult<FlagWrap<T>, E>`
+///
+/// ## On `impl` block or inline `mod`
+/// every `fn` inside returning `Result`, `RespResult`, `Option`, `ControlFlow` or an explicit `()`
+/// will be converted with the same args, unless it has its own `resp_result` attribute,
+/// e.g. `#[rresult(skip)]`, other functions are kept unchanged
 #[proc_macro_attribute]
 pub fn resp_result(
-    _: proc_macro::TokenStream, input: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let func = syn::parse_macro_input!(input as Function);
-    quote!(#func).into()
+    let args = syn::parse_macro_input!(args as AttrArgs);
+    let item = syn::parse_macro_input!(input as RespResultItem);
+    item.expand(args)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
 }
 
 #[proc_macro_derive(RespError, attributes(resp_result))]
@@ -32,3 +56,43 @@ pub fn derive_resp_error(
         .unwrap_or_else(|err| err.into_compile_error());
     token_stream.into()
 }
+
+/// implement `ToInner` for the custom extractor, thus it can be used with `MapReject`
+///
+/// - the tuple struct with exactly one field
+/// - the named struct with one field marked `#[inner]`, or with only one field
+#[proc_macro_derive(ToInner, attributes(inner))]
+pub fn derive_to_inner(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    gen_to_inner_derive(&input)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// implement `FromRequestParts` for the struct composed of several extractors,
+/// all the fields are extracted and the rejections are merged into one by `MergeRejection`
+///
+/// - `#[resp_result(rejection(E))]` on struct: the rejection type, rejecting with `RespResult<Nil, E>`
+/// - `#[resp_result(via(Extractor))]` on field: extract by `MapReject<Extractor<T>, E>` and take
+///   the inner value, otherwise the field type itself is the extractor
+///
+/// `E` must implement `MergeRejection`. `SimpleRespError`, `DynRespError` and `RejectionError`
+/// collect the details of all the failed fields, the errors derived by `RespError` with
+/// `#[resp_result(merge_first)]` keep the first rejection
+#[proc_macro_derive(RespFromRequestParts, attributes(resp_result))]
+pub fn derive_from_request_parts(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    gen_from_request_derive(&input, Extractor::Parts)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// like [`RespFromRequestParts`](macro@RespFromRequestParts), implement `FromRequest` with
+/// the field marked `#[resp_result(body)]` extracted from the whole request at last
+#[proc_macro_derive(RespFromRequest, attributes(resp_result))]
+pub fn derive_from_request(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    gen_from_request_derive(&input, Extractor::Request)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
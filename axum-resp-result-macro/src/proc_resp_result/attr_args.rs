@@ -0,0 +1,206 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Expr, ExprLit, Ident, Lit, LitStr, Token,
+};
+
+use super::instrument::Instrument;
+use crate::derive_resp_error::structure::http_code::HttpCode;
+
+/// the args of attribute `resp_result`
+#[derive(Default, Clone)]
+pub struct AttrArgs {
+    /// keep the item unchanged
+    pub skip: bool,
+    /// overwrite the status code on success
+    status: Option<Expr>,
+    /// insert headers on success
+    headers: Vec<(LitStr, Expr)>,
+    /// empty the body on success
+    empty_body: bool,
+    /// convert `Ok(None)` of `Result<Option<T>, E>` into `NotFoundError`
+    pub not_found: bool,
+    /// open a tracing span for the handler
+    pub instrument: Option<Instrument>,
+}
+
+impl AttrArgs {
+    /// whether need wrapping the success value with flags
+    pub fn has_flags(&self) -> bool {
+        self.status.is_some() || !self.headers.is_empty() || self.empty_body
+    }
+
+    /// generate the expr of `ExtraFlags`
+    pub fn flags(&self) -> TokenStream {
+        let status = self
+            .status
+            .iter()
+            .map(|code| quote!(__flags += ::axum_resp_result::ExtraFlag::status(#code);));
+        let headers = self.headers.iter().map(
+            |(name, value)| quote!(__flags += ::axum_resp_result::ExtraFlag::insert_header(#name, #value);),
+        );
+        let empty_body = self
+            .empty_body
+            .then(|| quote!(__flags += ::axum_resp_result::ExtraFlag::empty_body();));
+
+        quote! {{
+            let mut __flags = ::axum_resp_result::ExtraFlags::from(());
+            #(#status)*
+            #(#headers)*
+            #empty_body
+            __flags
+        }}
+    }
+}
+
+struct HeaderPair {
+    name: LitStr,
+    value: Expr,
+}
+
+impl Parse for HeaderPair {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
+impl Parse for AttrArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            match ident.to_string().as_str() {
+                "status" => {
+                    input.parse::<Token![=]>()?;
+                    if args.status.is_some() {
+                        return Err(syn::Error::new(ident.span(), "duplicate arg `status`"));
+                    }
+                    args.status = Some(input.parse::<HttpCode>()?.try_into()?);
+                }
+                "header" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let pairs = Punctuated::<HeaderPair, Token![,]>::parse_terminated(&content)?;
+                    for HeaderPair { name, value } in pairs {
+                        http::HeaderName::try_from(name.value())
+                            .map_err(|_| syn::Error::new(name.span(), "invalid header name"))?;
+                        // 字面量的响应头值也在编译期检查
+                        if let Expr::Lit(ExprLit {
+                            lit: Lit::Str(value),
+                            ..
+                        }) = &value
+                        {
+                            http::HeaderValue::try_from(value.value()).map_err(|_| {
+                                syn::Error::new(value.span(), "invalid header value")
+                            })?;
+                        }
+                        args.headers.push((name, value));
+                    }
+                }
+                "empty_body" => args.empty_body = true,
+                "not_found" => args.not_found = true,
+                "skip" => args.skip = true,
+                "instrument" => {
+                    if !cfg!(feature = "trace") {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "arg `instrument` require the `trace` feature",
+                        ));
+                    }
+                    args.instrument = Some(if input.peek(token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        Instrument::default()
+                    });
+                }
+                _ => return Err(syn::Error::new(
+                    ident.span(),
+                    "unknown arg, expect `status`, `header`, `empty_body`, `not_found`, `instrument` or `skip`",
+                )),
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::AttrArgs;
+
+    #[test]
+    fn test_parse_args() {
+        const ARGS: &str = r#"status = 201, header("location" = format!("/users/{}", 1), "etag" = "1"), empty_body"#;
+
+        let args = syn::parse_str::<AttrArgs>(ARGS).unwrap();
+
+        let status = args.status.as_ref().unwrap();
+        assert_eq!(
+            quote!(#status).to_string(),
+            ":: axum_resp_result :: StatusCode :: from_u16 (201u16) . unwrap ()"
+        );
+        let headers = args
+            .headers
+            .iter()
+            .map(|(name, value)| (name.value(), quote!(#value).to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            headers,
+            [
+                (
+                    "location".to_owned(),
+                    r#"format ! ("/users/{}" , 1)"#.to_owned()
+                ),
+                ("etag".to_owned(), r#""1""#.to_owned()),
+            ]
+        );
+        assert!(args.empty_body);
+        assert!(args.has_flags());
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_parse_instrument() {
+        let args = syn::parse_str::<AttrArgs>("instrument").unwrap();
+        assert!(args.instrument.is_some());
+        assert!(!args.has_flags());
+
+        const ARGS: &str =
+            r#"instrument(level = "debug", err_level = "warn", skip(password)), status = 201"#;
+        let args = syn::parse_str::<AttrArgs>(ARGS).unwrap();
+        assert!(args.instrument.is_some());
+        assert!(args.has_flags());
+
+        assert!(syn::parse_str::<AttrArgs>(r#"instrument(level = "fatal")"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_args() {
+        assert!(syn::parse_str::<AttrArgs>("status = 201, foo").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_header() {
+        for args in [
+            r#"header("bad header" = "1")"#,
+            r#"header("" = "1")"#,
+            r#"header("x-user" = "bad\nvalue")"#,
+        ] {
+            assert!(syn::parse_str::<AttrArgs>(args).is_err(), "{args}");
+        }
+        assert!(syn::parse_str::<AttrArgs>(r#"header("X-User" = user.name())"#).is_ok());
+    }
+}
@@ -1,18 +1,54 @@
+use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
+
+use super::attr_args::AttrArgs;
 use syn::{
-    parse::Parse, spanned::Spanned, Attribute, Block, ItemFn, Pat, PatIdent, ReturnType, Signature,
-    Visibility,
+    parse::Parse, Attribute, Block, FnArg, ItemFn, Pat, PatIdent, ReturnType, Signature, Visibility,
 };
 
 pub struct Function {
     attrs: Vec<Attribute>,
     vis: Visibility,
-    inner_sig: Signature,
     outer_sig: Signature,
     block: Box<Block>,
     args: Vec<syn::Ident>,
-    inner_ident: syn::Ident,
+    /// the original patterns of args need rebinding
+    binds: Vec<(Pat, syn::Ident)>,
+    ret_type: TokenStream,
+    /// the `Fallible` type of return, wrapped by `OrNotFound` with arg `not_found`
+    fallible_type: TokenStream,
+    or_not_found: bool,
     is_async: bool,
+    attr: AttrArgs,
+}
+
+impl Function {
+    /// apply the args of the attribute
+    pub fn with_args(mut self, attr: AttrArgs) -> syn::Result<Self> {
+        if attr.not_found {
+            let ret_type = &self.ret_type;
+            self.fallible_type = quote!(::axum_resp_result::OrNotFound<#ret_type>);
+            self.or_not_found = true;
+        }
+        let fallible_type = &self.fallible_type;
+        let success = quote!(<#fallible_type as ::axum_resp_result::Fallible>::Success);
+        let success = if attr.has_flags() {
+            quote!(::axum_resp_result::FlagWrap<#success>)
+        } else {
+            success
+        };
+        self.outer_sig.output = syn::parse2(quote! {
+            -> ::axum_resp_result::RespResult<
+                #success,
+                <#fallible_type as ::axum_resp_result::Fallible>::Failure,
+            >
+        })?;
+        if let Some(instrument) = &attr.instrument {
+            instrument.check_skip(&self.args)?;
+        }
+        self.attr = attr;
+        Ok(self)
+    }
 }
 
 impl ToTokens for Function {
@@ -20,29 +56,67 @@ impl ToTokens for Function {
         let Function {
             attrs,
             vis,
-            inner_sig,
             block,
             outer_sig,
             args,
-            inner_ident,
+            binds,
+            ret_type,
+            or_not_found,
             is_async,
+            attr,
+            ..
         } = self;
 
-        let ay = if *is_async {
-            Some(quote!(.await))
+        let instrument = attr.instrument.as_ref();
+        let span = instrument.map(|instrument| {
+            let span = instrument.span(&outer_sig.ident, args);
+            quote!(let __span = #span;)
+        });
+        // run the handler inside the span
+        let in_span = |call: TokenStream| match (instrument, *is_async) {
+            (None, true) => quote!(#call.await),
+            (None, false) => quote!(#call()),
+            (Some(_), true) => quote! {
+                ::axum_resp_result::__private::tracing::Instrument::instrument(#call, __span.clone()).await
+            },
+            (Some(_), false) => quote!(__span.in_scope(#call)),
+        };
+
+        // the nested function can not see `self`, `Self` or the generic params of impl,
+        // using closure or async block instead
+        let binds = binds.iter().map(|(pat, arg)| quote!(let #pat = #arg;));
+        let stmts = &block.stmts;
+        let call = if *is_async {
+            in_span(quote!(async move { #(#binds)* #(#stmts)* }))
         } else {
-            None
+            in_span(quote!((move || { #(#binds)* #(#stmts)* })))
+        };
+        let wrap = or_not_found.then(|| quote!(let __tmp = ::axum_resp_result::OrNotFound(__tmp);));
+        let call = quote! {
+            let __tmp: #ret_type = #call;
+            #wrap
+        };
+        let record = instrument.map(|instrument| instrument.record());
+        let ret = if attr.has_flags() {
+            let flags = attr.flags();
+            quote! {
+                ::axum_resp_result::RespResult::with_flags(
+                    ::axum_resp_result::IntoRespResult::into_rresult(__tmp),
+                    #flags,
+                )
+            }
+        } else {
+            quote!(::axum_resp_result::IntoRespResult::into_rresult(__tmp))
         };
-        let inner = quote!(#inner_sig #block);
         let outer = quote! {
             #(#attrs)*
             #vis #outer_sig
             {
-                #inner
-
-                let __tmp = #inner_ident(#(#args),*)#ay;
+                #span
+                #call
                 let __tmp = ::axum_resp_result::Fallible::to_result(__tmp);
-                ::axum_resp_result::IntoRespResult::into_rresult(__tmp)
+                #record
+                #ret
             }
         };
 
@@ -52,22 +126,20 @@ impl ToTokens for Function {
 
 impl Parse for Function {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let ItemFn {
+        Self::from_item(input.parse::<ItemFn>()?)
+    }
+}
+
+impl Function {
+    pub fn from_item(
+        ItemFn {
             attrs,
             vis,
             sig,
             block,
-        } = input.parse::<ItemFn>()?;
-
-        let mut inner_sig = sig;
-        if inner_sig.receiver().is_some() {
-            return Err(syn::Error::new(
-                inner_sig.receiver().span(),
-                "macro `resp-result` not support for method",
-            ));
-        }
-
-        let mut outer_sig = inner_sig.clone();
+        }: ItemFn,
+    ) -> syn::Result<Self> {
+        let mut outer_sig = sig;
 
         // set return type
         let ret = outer_sig.output;
@@ -75,53 +147,176 @@ impl Parse for Function {
             syn::ReturnType::Default => quote!(()),
             syn::ReturnType::Type(_, ty) => quote!(#ty),
         };
+        let fallible_type = ret_type.clone();
 
         let new_ret = quote! {
             ::axum_resp_result::RespResult<
-                <#ret_type as ::axum_resp_result::Fallible>::Success,
-                <#ret_type as ::axum_resp_result::Fallible>::Failure,
+                <#fallible_type as ::axum_resp_result::Fallible>::Success,
+                <#fallible_type as ::axum_resp_result::Fallible>::Failure,
             >
         };
 
-        let new_ret = syn::parse::<ReturnType>(quote!(-> #new_ret).into())?;
-        let is_async = inner_sig.asyncness.is_some();
+        let new_ret = syn::parse2::<ReturnType>(quote!(-> #new_ret))?;
+        let is_async = outer_sig.asyncness.is_some();
         outer_sig.output = new_ret;
 
         let mut args = Vec::new();
-        // set arg lists
+        let mut binds = Vec::new();
+        // set arg lists, the receiver keep unchanged
         for (idx, ty) in outer_sig
             .inputs
             .iter_mut()
-            .map(|v| match v {
-                syn::FnArg::Typed(ty) => ty,
-                syn::FnArg::Receiver(_) => unreachable!(),
+            .filter_map(|v| match v {
+                FnArg::Typed(ty) => Some(ty),
+                FnArg::Receiver(_) => None,
             })
             .enumerate()
         {
-            let ident = format_ident!("arg_{idx}");
+            // keep the plain identifier, other pattern using `arg_N`
+            let (ident, attrs, rebind) = match &*ty.pat {
+                Pat::Ident(PatIdent {
+                    attrs,
+                    by_ref: None,
+                    mutability,
+                    ident,
+                    subpat: None,
+                }) => (ident.clone(), attrs.clone(), mutability.is_some()),
+                _ => (format_ident!("arg_{idx}"), Vec::new(), true),
+            };
             args.push(ident.clone());
             let pat = Pat::Ident(PatIdent {
-                attrs: vec![],
+                attrs,
                 by_ref: None,
                 mutability: None,
-                ident,
+                ident: ident.clone(),
                 subpat: None,
             });
 
-            ty.pat = Box::new(pat)
+            let origin = std::mem::replace(&mut *ty.pat, pat);
+            if rebind {
+                binds.push((origin, ident));
+            }
         }
-        // set inner ident
-        let inner_ident = format_ident!("__inner_func");
-        inner_sig.ident = inner_ident.clone();
         Ok(Self {
             attrs,
             vis,
-            inner_sig,
             block,
             outer_sig,
             args,
-            inner_ident,
+            binds,
+            ret_type,
+            fallible_type,
+            or_not_found: false,
             is_async,
+            attr: AttrArgs::default(),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use quote::{quote, ToTokens};
+    use syn::ImplItemFn;
+
+    use super::Function;
+    use crate::proc_resp_result::AttrArgs;
+
+    #[test]
+    fn test_method() {
+        const CODE: &str = r#"
+            async fn get(&self, (a, b): (i32, i64), mut foo: String) -> Result<i32, Error> {
+                foo.push_str("bar");
+                Ok(self.0 + a + b as i32)
+            }
+        "#;
+
+        let func = syn::parse_str::<Function>(CODE).unwrap();
+        let method = syn::parse2::<ImplItemFn>(quote!(#func)).unwrap();
+        assert!(method.sig.receiver().is_some());
+
+        let body = method.block.to_token_stream().to_string();
+        assert!(body.contains("async move {"));
+        assert!(body.contains("let (a , b) = arg_0 ;"));
+        assert!(body.contains("let mut foo = foo ;"));
+        assert!(!body.contains("__inner_func"));
+    }
+
+    #[test]
+    fn test_associated() {
+        const CODE: &str = r#"
+            fn make() -> Result<Self, Error> {
+                Ok(Self)
+            }
+        "#;
+
+        let func = syn::parse_str::<Function>(CODE).unwrap();
+        let code = func.to_token_stream().to_string();
+        // `Self` used inside the closure, not a nested function
+        assert!(code.contains("let __tmp : Result < Self , Error > = (move ||"));
+        assert!(code.contains("{ Ok (Self) }) () ;"));
+        assert!(!code.contains("__inner_func"));
+    }
+
+    #[test]
+    fn test_not_found() {
+        const CODE: &str = r#"
+            async fn find(id: u32) -> Result<Option<User>, Error> {
+                Ok(None)
+            }
+        "#;
+
+        // `Ok(None)` kept as success without the arg
+        let func = syn::parse_str::<Function>(CODE).unwrap();
+        assert!(!func.or_not_found);
+        assert!(!func.to_token_stream().to_string().contains("OrNotFound"));
+
+        let args = syn::parse_str::<AttrArgs>("not_found").unwrap();
+        let func = syn::parse_str::<Function>(CODE)
+            .unwrap()
+            .with_args(args)
+            .unwrap();
+        assert!(func.or_not_found);
+        let code = func.to_token_stream().to_string();
+        assert!(code.contains(
+            "< :: axum_resp_result :: OrNotFound < Result < Option < User > , Error > > as :: axum_resp_result :: Fallible > :: Success"
+        ));
+        assert!(code.contains("let __tmp = :: axum_resp_result :: OrNotFound (__tmp) ;"));
+    }
+
+    #[test]
+    fn test_keep_pattern() {
+        const CODE: &str = r#"
+            async fn list<T: Default, const N: usize>(
+                #[allow(unused)] (a, b): (i32, i64),
+                mut foo: impl Into<String>,
+                bar: u8,
+            ) -> Result<T, Error>
+            where
+                T: Send,
+            {
+                Ok(T::default())
+            }
+        "#;
+
+        let func = syn::parse_str::<Function>(CODE).unwrap();
+        let args = func
+            .outer_sig
+            .inputs
+            .iter()
+            .map(|arg| match arg {
+                syn::FnArg::Typed(ty) => quote!(#ty).to_string(),
+                syn::FnArg::Receiver(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            [
+                "# [allow (unused)] arg_0 : (i32 , i64)",
+                "foo : impl Into < String >",
+                "bar : u8"
+            ]
+        );
+        assert_eq!(func.binds.len(), 2);
+    }
+}
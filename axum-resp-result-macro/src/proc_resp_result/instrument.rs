@@ -0,0 +1,130 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+/// the `instrument(...)` arg of attribute `resp_result`
+///
+/// - `level = "info"`: the level of the span, default `info`
+/// - `err_level = "error"`: the level of the event recording `RespResult::Err`, default `error`
+/// - `skip(arg, ...)`: not record the args
+/// - `skip_all`: not record any arg
+#[derive(Clone)]
+pub struct Instrument {
+    level: Ident,
+    err_level: Ident,
+    skip: Vec<Ident>,
+    skip_all: bool,
+}
+
+impl Default for Instrument {
+    fn default() -> Self {
+        Self {
+            level: Ident::new("INFO", Span::call_site()),
+            err_level: Ident::new("ERROR", Span::call_site()),
+            skip: Vec::new(),
+            skip_all: false,
+        }
+    }
+}
+
+fn parse_level(input: ParseStream) -> syn::Result<Ident> {
+    input.parse::<Token![=]>()?;
+    let level = input.parse::<LitStr>()?;
+    match level.value().to_lowercase().as_str() {
+        lv @ ("trace" | "debug" | "info" | "warn" | "error") => {
+            Ok(Ident::new(&lv.to_uppercase(), level.span()))
+        }
+        _ => Err(syn::Error::new(
+            level.span(),
+            "unknown level, expect `trace`, `debug`, `info`, `warn` or `error`",
+        )),
+    }
+}
+
+impl Parse for Instrument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut instrument = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            match ident.to_string().as_str() {
+                "level" => instrument.level = parse_level(input)?,
+                "err_level" => instrument.err_level = parse_level(input)?,
+                "skip" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let args = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                    instrument.skip.extend(args);
+                }
+                "skip_all" => instrument.skip_all = true,
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "unknown arg, expect `level`, `err_level`, `skip` or `skip_all`",
+                    ))
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(instrument)
+    }
+}
+
+impl Instrument {
+    /// check all the skipped args exist
+    pub fn check_skip(&self, args: &[Ident]) -> syn::Result<()> {
+        match self.skip.iter().find(|skip| !args.contains(skip)) {
+            Some(skip) => Err(syn::Error::new(
+                skip.span(),
+                format!("arg `{skip}` not found in the function"),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// generate the span named after the handler, recording the args not skipped
+    pub fn span(&self, name: &Ident, args: &[Ident]) -> TokenStream {
+        let level = &self.level;
+        let name = LitStr::new(&name.to_string(), name.span());
+        let fields = args
+            .iter()
+            .filter(|arg| !self.skip_all && !self.skip.contains(arg))
+            .map(|arg| quote!(#arg = ?#arg));
+        quote! {
+            ::axum_resp_result::__private::tracing::span!(
+                ::axum_resp_result::__private::tracing::Level::#level,
+                #name,
+                #(#fields,)*
+            )
+        }
+    }
+
+    /// generate the statement recording the outcome of `__tmp`
+    pub fn record(&self) -> TokenStream {
+        let level = &self.level;
+        let err_level = &self.err_level;
+        quote! {
+            match &__tmp {
+                ::core::result::Result::Ok(_) => ::axum_resp_result::__private::tracing::event!(
+                    parent: &__span,
+                    ::axum_resp_result::__private::tracing::Level::#level,
+                    outcome = "Success",
+                ),
+                ::core::result::Result::Err(__err) => ::axum_resp_result::__private::tracing::event!(
+                    parent: &__span,
+                    ::axum_resp_result::__private::tracing::Level::#err_level,
+                    outcome = "Error",
+                    http_code = ::axum_resp_result::RespError::http_code(__err).as_u16(),
+                    error = %::axum_resp_result::RespError::log_message(__err),
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::Parse, spanned::Spanned, Attribute, ImplItem, ImplItemFn, Item, ItemFn, ReturnType,
+    Signature, Type, TypePath,
+};
+
+use super::{attr_args::AttrArgs, function_loader::Function};
+
+/// the item that the attribute `resp_result` applied on
+pub enum RespResultItem {
+    /// a single function or method
+    Fn(ItemFn),
+    /// an `impl` block or an inline `mod`, all the handlers inside will be converted
+    Block(Item),
+}
+
+impl Parse for RespResultItem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        match input.parse::<Item>()? {
+            Item::Fn(func) => Ok(Self::Fn(func)),
+            item @ Item::Impl(_) => Ok(Self::Block(item)),
+            Item::Mod(item) if item.content.is_some() => Ok(Self::Block(Item::Mod(item))),
+            Item::Mod(item) => Err(syn::Error::new(
+                item.span(),
+                "`resp_result` can only be used on inline `mod`",
+            )),
+            // e.g. the `default fn` in impl block
+            Item::Verbatim(tokens) => {
+                let func = syn::parse2::<ImplItemFn>(tokens)?;
+                Ok(Self::Fn(into_item_fn(func)))
+            }
+            item => Err(syn::Error::new(
+                item.span(),
+                "`resp_result` can only be used on `fn`, `impl` block or inline `mod`",
+            )),
+        }
+    }
+}
+
+impl RespResultItem {
+    /// apply the args of the attribute and generate the code
+    pub fn expand(self, args: AttrArgs) -> syn::Result<TokenStream> {
+        if args.skip {
+            return Ok(self.into_token_stream());
+        }
+        match self {
+            RespResultItem::Fn(func) => {
+                let func = Function::from_item(func)?.with_args(args)?;
+                Ok(quote!(#func))
+            }
+            RespResultItem::Block(item) if args.not_found => Err(syn::Error::new(
+                item.span(),
+                "arg `not_found` can only be used on `fn`",
+            )),
+            RespResultItem::Block(mut item) => {
+                expand_item(&mut item, &args)?;
+                Ok(quote!(#item))
+            }
+        }
+    }
+}
+
+impl ToTokens for RespResultItem {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            RespResultItem::Fn(func) => func.to_tokens(tokens),
+            RespResultItem::Block(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+/// convert all the handlers inside the impl block or mod,
+/// the nested impl block and mod also be converted
+fn expand_item(item: &mut Item, args: &AttrArgs) -> syn::Result<()> {
+    match item {
+        Item::Fn(func) if need_expand(&func.attrs, &func.sig) => {
+            let func = Function::from_item(func.clone())?.with_args(args.clone())?;
+            *item = Item::Verbatim(quote!(#func));
+        }
+        Item::Impl(item_impl) => {
+            for impl_item in &mut item_impl.items {
+                let ImplItem::Fn(func) = impl_item else {
+                    continue;
+                };
+                if need_expand(&func.attrs, &func.sig) {
+                    let func =
+                        Function::from_item(into_item_fn(func.clone()))?.with_args(args.clone())?;
+                    *impl_item = ImplItem::Verbatim(quote!(#func));
+                }
+            }
+        }
+        Item::Mod(item_mod) => {
+            if let Some((_, items)) = &mut item_mod.content {
+                for item in items {
+                    expand_item(item, args)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// every `fn` returning a `Fallible` type and not annotated by `resp_result` itself
+/// will be converted, the others are kept unchanged.
+///
+/// the `fn` can opt out with `#[rresult(skip)]`
+fn need_expand(attrs: &[Attribute], sig: &Signature) -> bool {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    is_fallible(ty) && !attrs.iter().any(is_resp_result_attr)
+}
+
+/// the type looks like `Result`, `RespResult`, `Option`, `ControlFlow` or an explicit `()`
+fn is_fallible(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last().is_some_and(|seg| {
+            ["Result", "RespResult", "Option", "ControlFlow"]
+                .iter()
+                .any(|name| seg.ident == name)
+        }),
+        Type::Tuple(tuple) => tuple.elems.is_empty(),
+        Type::Paren(paren) => is_fallible(&paren.elem),
+        Type::Group(group) => is_fallible(&group.elem),
+        _ => false,
+    }
+}
+
+fn is_resp_result_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "rresult" || seg.ident == "resp_result")
+}
+
+fn into_item_fn(
+    ImplItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+        ..
+    }: ImplItemFn,
+) -> ItemFn {
+    ItemFn {
+        attrs,
+        vis,
+        sig,
+        block: Box::new(block),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use syn::{ImplItem, ImplItemFn, ItemImpl};
+
+    use super::RespResultItem;
+    use crate::proc_resp_result::AttrArgs;
+
+    #[test]
+    fn test_impl_block() {
+        const CODE: &str = r#"
+            impl Controller {
+                pub fn new() -> Self {
+                    Self
+                }
+
+                pub async fn get(&self, id: u32) -> Result<User, Error> {
+                    self.find(id).await.ok_or(Error)
+                }
+
+                #[rresult(skip)]
+                pub async fn find_raw(&self) -> Result<User, Error> {
+                    todo!()
+                }
+
+                pub async fn helper(&self) {}
+
+                #[rresult(skip)]
+                async fn find(&self, id: u32) -> Option<User> {
+                    None
+                }
+
+                pub async fn name(&self) -> String {
+                    self.name.clone()
+                }
+
+                pub(crate) async fn users(&self) -> Option<Vec<User>> {
+                    None
+                }
+
+                async fn count(&self) -> Result<u64, Error> {
+                    Ok(0)
+                }
+
+                pub fn validate(&self) -> Result<(), Error> {
+                    Ok(())
+                }
+
+                pub async fn ping(&self) -> () {}
+
+                pub async fn flow(&self) -> std::ops::ControlFlow<User> {
+                    std::ops::ControlFlow::Continue(())
+                }
+            }
+        "#;
+
+        let item = syn::parse_str::<RespResultItem>(CODE).unwrap();
+        let tokens = item.expand(AttrArgs::default()).unwrap();
+        let item_impl = syn::parse2::<ItemImpl>(tokens).unwrap();
+
+        let converted = item_impl
+            .items
+            .iter()
+            .map(|item| match item {
+                ImplItem::Fn(func) => quote::quote!(#func)
+                    .to_string()
+                    .contains(":: axum_resp_result :: RespResult"),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            converted,
+            [false, true, false, false, false, false, true, true, true, true, true]
+        );
+
+        // the skipped fn kept unchanged
+        let ImplItem::Fn(find) = &item_impl.items[4] else {
+            unreachable!()
+        };
+        let expect = syn::parse_str::<ImplItemFn>(
+            r#"#[rresult(skip)]
+            async fn find(&self, id: u32) -> Option<User> {
+                None
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(find, &expect);
+    }
+}
@@ -1,3 +1,7 @@
+mod attr_args;
 mod function_loader;
+mod instrument;
+mod item_loader;
 
-pub use function_loader::Function;
+pub use attr_args::AttrArgs;
+pub use item_loader::RespResultItem;
@@ -1,10 +1,12 @@
-use axum::{body::Body, routing::get, Router};
+use axum::{routing::get, Router};
 use config::AxumConfig;
 use echo::echo_number;
 use error::PlainError;
-use http::Request;
 
-use axum_resp_result::{set_config, RespResult};
+use axum_resp_result::{
+    catch_panic_layer, fallback, method_not_allowed, set_config, RespContextLayer, RespResult,
+    WrapErrorLayer,
+};
 use tokio::net::TcpListener;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnRequest, TraceLayer};
 use tracing::{metadata::LevelFilter, Level};
@@ -36,19 +38,23 @@ async fn main() {
 
     set_config(&AxumConfig);
 
-    let addr = SocketAddr::try_from(([127, 0, 0, 1], 5000u16)).unwrap();
+    let addr = SocketAddr::from(([127, 0, 0, 1], 5000u16));
 
     let router = Router::new()
-        .route("/echo/:num", get(echo_number))
+        .route("/echo/{num}", get(echo_number))
         .route("/want_304", get(want_304))
         .nest(
             "/parse",
             Router::new()
-                .route("/i32/:v", get(parse_to_i32))
-                .route("/i64/:v/:v2", get(parse_to_i64)),
+                .route("/i32/{v}", get(parse_to_i32))
+                .route("/i64/{v}/{v2}", get(parse_to_i64)),
         )
-        .route("/panic", get(|| async { panic!("Panic it") }))
-        .fallback(fallback)
+        .route("/panic", get(panic_handler))
+        .fallback(fallback::<PlainError>)
+        .method_not_allowed_fallback(method_not_allowed::<PlainError>)
+        .layer(catch_panic_layer::<PlainError>())
+        .layer(WrapErrorLayer::new())
+        .layer(RespContextLayer::new())
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
@@ -66,12 +72,29 @@ mod error {
     use std::{borrow::Cow, num::ParseIntError};
 
     use axum::extract::rejection::PathRejection;
-    use axum_resp_result::RespError;
+    use axum_resp_result::{PanicError, RespError, RouteError};
     use http::StatusCode;
 
     pub(super) struct PlainError {
         pub(super) msg: String,
         pub(super) code: u32,
+        pub(super) status: StatusCode,
+    }
+
+    impl From<PanicError> for PlainError {
+        fn from(err: PanicError) -> Self {
+            let mut plain = Self::new(err.resp_message().into(), 500);
+            plain.status = err.http_code();
+            plain
+        }
+    }
+
+    impl From<RouteError> for PlainError {
+        fn from(err: RouteError) -> Self {
+            let mut plain = Self::new(err.message().into(), 1000);
+            plain.status = err.http_code();
+            plain
+        }
     }
 
     impl From<PathRejection> for PlainError {
@@ -88,7 +111,11 @@ mod error {
 
     impl PlainError {
         pub(super) fn new(msg: String, code: u32) -> Self {
-            Self { msg, code }
+            Self {
+                msg,
+                code,
+                status: StatusCode::BAD_REQUEST,
+            }
         }
     }
 
@@ -98,7 +125,7 @@ mod error {
         }
 
         fn http_code(&self) -> http::StatusCode {
-            StatusCode::BAD_REQUEST
+            self.status
         }
 
         type ExtraMessage = u32;
@@ -130,7 +157,7 @@ mod echo {
     pub(super) struct Input {
         num: i32,
     }
-    #[rresult]
+    #[rresult(instrument(skip_all))]
     pub(super) async fn echo_number(
         MapReject(Input { num }): MapReject<Path<Input>, PlainError>,
     ) -> Result<String, PlainError> {
@@ -183,18 +210,16 @@ mod rtry_router {
     }
 }
 
-async fn fallback(req: Request<Body>) -> PlainRResult<()> {
-    Err(PlainError::new(
-        format!("Router not exist {}", req.uri()),
-        1000,
-    ))
-    .into()
+async fn panic_handler() {
+    panic!("Panic it")
 }
 
 mod config {
     use std::borrow::Cow;
 
-    use axum_resp_result::{ConfigTrait, RespConfig, SerdeConfig, SignType, StatusSign};
+    use axum_resp_result::{
+        ConfigTrait, MetaField, MetaType, RespConfig, SerdeConfig, SignType, StatusSign,
+    };
 
     pub(super) struct AxumConfig;
 
@@ -214,6 +239,14 @@ mod config {
         fn err_msg_name(&self) -> Cow<'static, str> {
             "message".into()
         }
+
+        fn meta_fields(&self) -> Vec<MetaField> {
+            vec![
+                MetaField::new("request-id", MetaType::RequestId),
+                MetaField::new("timestamp", MetaType::Timestamp),
+                MetaField::new("server-time-ms", MetaType::ServerTimeMs),
+            ]
+        }
     }
 
     impl RespConfig for AxumConfig {
@@ -1,14 +1,18 @@
-use axum_resp_result::RespError;
+use axum_resp_result::{DynRespError, RespError};
 use error::PlainError;
 fn main() {
+    let _ret = Controller(3).get(String::from("12"));
+    println!("{_ret:?}");
     let _ret = test((1, 2), String::new());
     match _ret {
         RespResult::Success(_) => {}
         RespResult::Err(err) => {
+            let err = DynRespError::from(err);
             println!(
-                "{:?},{:?}, {:?}",
+                "{:?},{:?},{:?}, {:?}",
                 err.resp_message(),
                 err.http_code(),
+                err.business_code(),
                 err.log_message()
             )
         }
@@ -20,20 +24,35 @@ mod error {
     use axum::extract::rejection::PathRejection;
     use axum_resp_result::RespError;
     #[derive(Debug, thiserror::Error, RespError)]
+    #[resp_result(dyn_error)]
     pub(super) enum PlainError {
         #[error("Parse Request Path Error: {0}")]
         #[resp_result(err_msg = "Parse Request Path Error", err_code = 400)]
         Path(#[from] PathRejection),
         #[error("Parse Int Error: {0}")]
-        #[resp_result(err_msg = "Invalid Input Integer", err_code = "Bad Request")]
+        #[resp_result(
+            err_msg = "Invalid Input Integer",
+            err_code = "Bad Request",
+            biz_code = 1002
+        )]
         ParseInt(#[from] ParseIntError),
     }
 }
 use axum_resp_result::{rresult, RespResult};
 #[rresult]
-fn test((a, b): (i32, i64), foo: String) -> Result<(), PlainError> {
-    println!("{a},{b},{foo}");
-    let a = foo.parse::<i32>()?;
+fn test((a, b): (i32, i64), input: String) -> Result<(), PlainError> {
+    println!("{a},{b},{input}");
+    let a = input.parse::<i32>()?;
     println!("{a:?}");
     Ok(())
 }
+
+struct Controller(i32);
+
+impl Controller {
+    #[rresult]
+    fn get(&self, input: String) -> Result<i32, PlainError> {
+        let a = input.parse::<i32>()?;
+        Ok(self.0 + a)
+    }
+}
@@ -0,0 +1,175 @@
+use std::{any::Any, marker::PhantomData};
+
+use axum::{
+    body::Body,
+    response::{IntoResponse, Response},
+};
+use tower_http::catch_panic::{CatchPanicLayer, ResponseForPanic};
+
+use crate::{Nil, PanicError, RespError, RespResult};
+
+/// read the message out of the payload of panic
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&'static str>() {
+        (*msg).to_owned()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+/// the [`ResponseForPanic`] responding the [`PanicError`] converted into `E`
+pub struct RespPanicHandler<E> {
+    _phantom: PhantomData<fn() -> E>,
+}
+
+impl<E> Clone for RespPanicHandler<E> {
+    fn clone(&self) -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E> std::fmt::Debug for RespPanicHandler<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RespPanicHandler").finish()
+    }
+}
+
+impl<E> ResponseForPanic for RespPanicHandler<E>
+where
+    E: From<PanicError> + RespError,
+{
+    type ResponseBody = Body;
+
+    fn response_for_panic(&mut self, err: Box<dyn Any + Send + 'static>) -> Response<Body> {
+        let err = PanicError::new(payload_message(&*err));
+        #[cfg(feature = "trace")]
+        let err = match location::take() {
+            Some(location) => err.with_location(location),
+            None => err,
+        };
+        #[cfg(feature = "trace")]
+        tracing::error!(
+            panic.payload = %err.payload(),
+            panic.location = err.location().unwrap_or("unknown"),
+            "Handler Panicked"
+        );
+        RespResult::<Nil, E>::Err(err.into()).into_response()
+    }
+}
+
+/// the [`CatchPanicLayer`] converting the panic of handler into
+/// `RespResult::Err` of `E`, the payload of panic never exposed in the response
+///
+/// with feature `trace`, the payload and location of panic are recorded by `tracing`,
+/// for the location a panic hook is installed once, which only stashes the location
+/// for the handler and then calls the previous hook
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use axum_resp_result::{catch_panic_layer, SimpleRespError};
+///
+/// async fn panic_handler() {
+///     panic!("Panic it")
+/// }
+///
+/// let router: Router = Router::new()
+///     .route("/panic", get(panic_handler))
+///     .layer(catch_panic_layer::<SimpleRespError>());
+/// ```
+pub fn catch_panic_layer<E>() -> CatchPanicLayer<RespPanicHandler<E>>
+where
+    E: From<PanicError> + RespError,
+{
+    #[cfg(feature = "trace")]
+    location::install_hook();
+    CatchPanicLayer::custom(RespPanicHandler {
+        _phantom: PhantomData,
+    })
+}
+
+/// the panic location stashed by the hook, the panic hook and [`ResponseForPanic`]
+/// run on the same thread
+#[cfg(feature = "trace")]
+mod location {
+    use std::{cell::RefCell, sync::Once};
+
+    thread_local! {
+        static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn install_hook() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let prev = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                let location = info.location().map(ToString::to_string);
+                LOCATION.with(|stash| *stash.borrow_mut() = location);
+                prev(info);
+            }));
+        });
+    }
+
+    pub(super) fn take() -> Option<String> {
+        LOCATION.with(|stash| stash.borrow_mut().take())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{body::Body, routing::get, Router};
+    use http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use super::catch_panic_layer;
+    use crate::SimpleRespError;
+
+    async fn panic_handler() {
+        panic!("secret in payload")
+    }
+
+    #[tokio::test]
+    async fn test_catch_panic() {
+        let router = Router::new()
+            .route("/panic", get(panic_handler))
+            .layer(catch_panic_layer::<SimpleRespError>());
+
+        let req = Request::get("/panic").body(Body::empty()).unwrap();
+        let resp = router.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error-message"], "Internal Server Error");
+        assert!(!body.to_string().contains("secret"));
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_panic_location() {
+        use tower_http::catch_panic::ResponseForPanic;
+
+        use super::{location, RespPanicHandler};
+        use crate::PanicError;
+
+        let _layer = catch_panic_layer::<PanicError>();
+        let payload = std::panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        let line = line!() - 1;
+        let location = location::take().unwrap();
+        assert!(location.starts_with(&format!("{}:{line}:", file!())));
+
+        // the handler takes the stashed location
+        let _ = std::panic::catch_unwind(|| panic!("boom"));
+        let resp = RespPanicHandler::<PanicError> {
+            _phantom: std::marker::PhantomData,
+        }
+        .response_for_panic(payload);
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(location::take().is_none());
+    }
+}
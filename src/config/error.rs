@@ -0,0 +1,32 @@
+/// the problem found when validating the config
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigProblem {
+    /// the header name of extra error message is invalid
+    #[error("invalid extra error header name `{name}`")]
+    InvalidHeaderName { name: String },
+    /// the field path is the same or the prefix of other field path
+    #[error("field path `{path}` conflicts with other field")]
+    FieldConflict { path: String },
+    /// the field path contains empty segment
+    #[error("field path `{path}` contains empty segment")]
+    EmptyField { path: String },
+}
+
+/// the error when set the config
+#[derive(Debug, thiserror::Error)]
+pub enum SetRespResultConfigureError {
+    /// the config has been set before
+    #[error("RespResult Configure has set")]
+    AlreadySet,
+    /// the config is invalid
+    #[error("RespResult Configure invalid: {}", join_problems(.0))]
+    Invalid(Vec<ConfigProblem>),
+}
+
+fn join_problems(problems: &[ConfigProblem]) -> String {
+    problems
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,188 @@
+use super::ConfigProblem;
+
+/// the kind of the field in response envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FieldKind {
+    Sign,
+    #[cfg(feature = "extra-error")]
+    ExtraMessage,
+    ErrMessage,
+    Details,
+    Body,
+    /// the index of the meta field
+    Meta(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct Leaf {
+    pub(crate) kind: FieldKind,
+    /// available when [`RespResult`](crate::RespResult) is `Success(_)`
+    pub(crate) on_ok: bool,
+    /// available when [`RespResult`](crate::RespResult) is `Err(_)`
+    pub(crate) on_err: bool,
+}
+
+impl Leaf {
+    pub(crate) const fn new(kind: FieldKind, on_ok: bool, on_err: bool) -> Self {
+        Self {
+            kind,
+            on_ok,
+            on_err,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum LayoutNode {
+    Field(Leaf),
+    Nested(Layout),
+}
+
+/// the layout tree of the response envelope, each level is an object
+#[derive(Debug, Default)]
+pub(crate) struct Layout {
+    pub(crate) entries: Vec<(&'static str, LayoutNode)>,
+    ok_size: usize,
+    err_size: usize,
+}
+
+impl Layout {
+    /// build the layout from the field paths, the path is split by `.`
+    pub(crate) fn build(
+        fields: impl IntoIterator<Item = (&'static str, Leaf)>,
+    ) -> Result<Self, Vec<ConfigProblem>> {
+        let mut root = Layout::default();
+        let mut problems = Vec::new();
+        for (path, leaf) in fields {
+            let segments = path.split('.').collect::<Vec<_>>();
+            if segments.iter().any(|seg| seg.is_empty()) {
+                problems.push(ConfigProblem::EmptyField { path: path.into() });
+            } else if root.insert(&segments, leaf).is_err() {
+                problems.push(ConfigProblem::FieldConflict { path: path.into() });
+            }
+        }
+        root.count_size();
+
+        if problems.is_empty() {
+            Ok(root)
+        } else {
+            Err(problems)
+        }
+    }
+
+    fn insert(&mut self, path: &[&'static str], leaf: Leaf) -> Result<(), Leaf> {
+        let Some((&key, rest)) = path.split_first() else {
+            return Err(leaf);
+        };
+
+        match (
+            self.entries.iter_mut().find(|(k, _)| *k == key),
+            rest.is_empty(),
+        ) {
+            (None, true) => self.entries.push((key, LayoutNode::Field(leaf))),
+            (None, false) => {
+                let mut nested = Layout::default();
+                nested.insert(rest, leaf)?;
+                self.entries.push((key, LayoutNode::Nested(nested)));
+            }
+            (Some((_, LayoutNode::Nested(nested))), false) => nested.insert(rest, leaf)?,
+            (Some(_), _) => return Err(leaf),
+        }
+        Ok(())
+    }
+
+    fn count_size(&mut self) {
+        let (mut ok_size, mut err_size) = (0, 0);
+        for (_, node) in &mut self.entries {
+            let (on_ok, on_err) = match node {
+                LayoutNode::Field(Leaf { on_ok, on_err, .. }) => (*on_ok, *on_err),
+                LayoutNode::Nested(nested) => {
+                    nested.count_size();
+                    (nested.ok_size > 0, nested.err_size > 0)
+                }
+            };
+            ok_size += on_ok as usize;
+            err_size += on_err as usize;
+        }
+        self.ok_size = ok_size;
+        self.err_size = err_size;
+    }
+
+    /// the number of fields of this level
+    pub(crate) fn size(&self, is_ok: bool) -> usize {
+        if is_ok {
+            self.ok_size
+        } else {
+            self.err_size
+        }
+    }
+
+    /// whether the body is a direct field of this level
+    pub(crate) fn contains_body(&self) -> bool {
+        self.entries.iter().any(
+            |(_, node)| matches!(node, LayoutNode::Field(leaf) if leaf.kind == FieldKind::Body),
+        )
+    }
+
+    /// whether the `key` is a field of this level except the body
+    pub(crate) fn is_sibling_of_body(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, node)| {
+            *k == key && !matches!(node, LayoutNode::Field(leaf) if leaf.kind == FieldKind::Body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FieldKind, Layout, LayoutNode, Leaf};
+    use crate::config::ConfigProblem;
+
+    fn leaf(kind: FieldKind) -> Leaf {
+        Leaf::new(kind, true, kind != FieldKind::Body)
+    }
+
+    #[test]
+    fn test_nested_layout() {
+        let layout = Layout::build([
+            ("status.ok", leaf(FieldKind::Sign)),
+            ("status.message", leaf(FieldKind::ErrMessage)),
+            ("data", leaf(FieldKind::Body)),
+        ])
+        .unwrap();
+
+        assert_eq!(layout.entries.len(), 2);
+        assert_eq!(layout.size(true), 2);
+        assert_eq!(layout.size(false), 1);
+        let LayoutNode::Nested(ref status) = layout.entries[0].1 else {
+            panic!("`status` is not nested")
+        };
+        assert_eq!(status.size(false), 2);
+    }
+
+    #[test]
+    fn test_conflict_layout() {
+        let problems = Layout::build([
+            ("status", leaf(FieldKind::Sign)),
+            ("status.message", leaf(FieldKind::ErrMessage)),
+            ("body", leaf(FieldKind::Body)),
+            ("body", leaf(FieldKind::Meta(0))),
+            ("meta..id", leaf(FieldKind::Meta(1))),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            problems,
+            [
+                ConfigProblem::FieldConflict {
+                    path: "status.message".into()
+                },
+                ConfigProblem::FieldConflict {
+                    path: "body".into()
+                },
+                ConfigProblem::EmptyField {
+                    path: "meta..id".into()
+                },
+            ]
+        );
+    }
+}
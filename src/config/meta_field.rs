@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use crate::{owner_leak::OwnerLeaker, resp_context::RespContext};
+
+#[derive(Debug, Clone)]
+/// an extra top-level field of the response envelope,
+/// its value is read from the [`RespContext`] of current request
+pub struct MetaField {
+    /// the field name of the meta field
+    pub(super) field_name: Cow<'static, str>,
+    /// the source of the meta field value
+    pub(super) ty: MetaType,
+}
+
+impl MetaField {
+    /// create a new [`MetaField`] with provide `name` and `ty`
+    pub fn new(name: impl Into<Cow<'static, str>>, ty: MetaType) -> Self {
+        Self {
+            field_name: name.into(),
+            ty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// the value source of the meta field
+///
+/// all the value will be serialized as `null` if the response
+/// is not generated inside the [`RespContextLayer`](crate::RespContextLayer),
+/// which require feature `context`
+pub enum MetaType {
+    /// the request id of current request, read from the request header
+    /// - `null` if the request not carry the request id header
+    RequestId,
+    /// the time of receiving the request, milliseconds since the unix epoch
+    Timestamp,
+    /// the milliseconds elapsed since receiving the request
+    ServerTimeMs,
+}
+
+pub(crate) struct InnerMetaField {
+    pub(crate) field: &'static str,
+    pub(crate) ty: MetaType,
+}
+
+impl From<MetaField> for InnerMetaField {
+    fn from(MetaField { field_name, ty }: MetaField) -> Self {
+        Self {
+            field: field_name.leak(),
+            ty,
+        }
+    }
+}
+
+impl InnerMetaField {
+    #[inline]
+    pub(crate) fn value<'c>(&self, ctx: Option<&'c RespContext>) -> MetaValue<'c> {
+        let Some(ctx) = ctx else {
+            return MetaValue::Null;
+        };
+        match self.ty {
+            MetaType::RequestId => ctx
+                .request_id()
+                .map(MetaValue::Str)
+                .unwrap_or(MetaValue::Null),
+            MetaType::Timestamp => MetaValue::Number(ctx.timestamp_ms()),
+            MetaType::ServerTimeMs => MetaValue::Number(ctx.elapsed_ms()),
+        }
+    }
+}
+
+pub(crate) enum MetaValue<'c> {
+    Null,
+    Str(&'c str),
+    Number(u64),
+}
+
+impl Serialize for MetaValue<'_> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MetaValue::Null => serializer.serialize_none(),
+            MetaValue::Str(s) => serializer.serialize_str(s),
+            MetaValue::Number(num) => serializer.serialize_u64(*num),
+        }
+    }
+}
@@ -1,10 +1,18 @@
+mod error;
+mod layout;
+mod meta_field;
 mod resp;
 mod status_signed;
 
+pub use self::error::{ConfigProblem, SetRespResultConfigureError};
+pub(crate) use self::layout::{FieldKind, Layout, LayoutNode};
+pub use self::meta_field::{MetaField, MetaType};
+use self::resp::InnerRespConfig;
 pub use self::resp::RespConfig;
+pub(crate) use self::serde::InnerSerdeConfig;
 pub use self::serde::SerdeConfig;
-pub use self::status_signed::{SignType, StatusSign};
-use self::{resp::InnerRespConfig, serde::InnerSerdeConfig};
+pub use self::status_signed::{CodeSource, SignType, StatusSign};
+use crate::expect_ext::ExpectExt;
 
 mod serde;
 
@@ -25,15 +33,24 @@ pub(crate) struct InnerConfig {
 
 impl Default for InnerConfig {
     fn default() -> Self {
-        Self::from_cfg(&DefaultConfig)
+        Self::from_cfg(&DefaultConfig).with_expect("Default Config Invalid")
     }
 }
 
 impl InnerConfig {
-    pub(crate) fn from_cfg<C: ConfigTrait>(cfg: &C) -> Self {
-        Self {
-            serde: InnerSerdeConfig::into_inner(cfg),
-            resp: InnerRespConfig::into_inner(cfg),
+    /// load the config, all the problems of the config will be collected
+    pub(crate) fn from_cfg<C: ConfigTrait>(cfg: &C) -> Result<Self, Vec<ConfigProblem>> {
+        match (
+            InnerSerdeConfig::into_inner(cfg),
+            InnerRespConfig::into_inner(cfg),
+        ) {
+            (Ok(serde), Ok(resp)) => Ok(Self { serde, resp }),
+            (serde, resp) => Err(serde
+                .err()
+                .into_iter()
+                .chain(resp.err())
+                .flatten()
+                .collect()),
         }
     }
 }
@@ -46,3 +63,44 @@ impl SerdeConfig for DefaultConfig {}
 impl RespConfig for DefaultConfig {}
 
 impl ConfigTrait for DefaultConfig {}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::{ConfigProblem, ConfigTrait, InnerConfig, RespConfig, SerdeConfig};
+
+    struct BadConfig;
+
+    impl SerdeConfig for BadConfig {
+        fn body_name(&self) -> Cow<'static, str> {
+            "message".into()
+        }
+
+        fn err_msg_name(&self) -> Cow<'static, str> {
+            "message".into()
+        }
+    }
+
+    impl RespConfig for BadConfig {
+        #[cfg(feature = "extra-error")]
+        fn head_extra_code(&self) -> Option<Cow<'static, str>> {
+            Some("Extra Error".into())
+        }
+    }
+
+    impl ConfigTrait for BadConfig {}
+
+    #[test]
+    fn test_invalid_config() {
+        let problems = InnerConfig::from_cfg(&BadConfig).err().unwrap();
+
+        assert!(problems.contains(&ConfigProblem::FieldConflict {
+            path: "message".into()
+        }));
+        #[cfg(feature = "extra-error")]
+        assert!(problems.contains(&ConfigProblem::InvalidHeaderName {
+            name: "Extra Error".into()
+        }));
+    }
+}
@@ -1,9 +1,9 @@
 #[allow(unused_imports)]
 use http::header::HeaderName;
-#[allow(unused_imports)]
+use http::StatusCode;
 use std::borrow::Cow;
 
-#[allow(unused_imports)]
+use super::ConfigProblem;
 use crate::owner_leak::OwnerLeaker;
 /// the config of response
 pub trait RespConfig {
@@ -11,25 +11,60 @@ pub trait RespConfig {
     /// - `Some(_)` enable
     /// - `None` disable
     ///
+    /// the name is case-insensitive, a name containing invalid char
+    /// will be rejected by [`try_set_config`](crate::try_set_config)
+    ///
     /// ## Default
     /// default is enable with name `extra-error`
     #[cfg(feature = "extra-error")]
     fn head_extra_code(&self) -> Option<Cow<'static, str>> {
         Some("extra-error".into())
     }
+
+    /// the http status code of [`NotFoundError`](crate::NotFoundError),
+    /// which is the failure of a handler returning `None`
+    ///
+    /// ## Default
+    /// default is `404 Not Found`
+    fn not_found_status(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+
+    /// the message of [`NotFoundError`](crate::NotFoundError)
+    ///
+    /// ## Default
+    /// default is `Resource Not Found`
+    fn not_found_message(&self) -> Cow<'static, str> {
+        "Resource Not Found".into()
+    }
 }
 
 pub(crate) struct InnerRespConfig {
     #[cfg(feature = "extra-error")]
     pub(crate) extra_code: Option<HeaderName>,
+    pub(crate) not_found_status: StatusCode,
+    pub(crate) not_found_message: &'static str,
 }
 
 impl InnerRespConfig {
-    #[allow(unused_variables)]
-    pub fn into_inner<C: RespConfig>(cfg: &C) -> Self {
-        Self {
-            #[cfg(feature = "extra-error")]
-            extra_code: cfg.head_extra_code().leak().map(HeaderName::from_static),
+    pub fn into_inner<C: RespConfig>(cfg: &C) -> Result<Self, Vec<ConfigProblem>> {
+        #[allow(unused_mut)]
+        let mut problems = Vec::new();
+        #[cfg(feature = "extra-error")]
+        let extra_code = cfg.head_extra_code().and_then(|name| {
+            HeaderName::try_from(name.as_ref())
+                .map_err(|_| problems.push(ConfigProblem::InvalidHeaderName { name: name.into() }))
+                .ok()
+        });
+
+        if !problems.is_empty() {
+            return Err(problems);
         }
+        Ok(Self {
+            #[cfg(feature = "extra-error")]
+            extra_code,
+            not_found_status: cfg.not_found_status(),
+            not_found_message: cfg.not_found_message().leak(),
+        })
     }
 }
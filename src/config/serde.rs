@@ -3,8 +3,10 @@ use std::borrow::Cow;
 use crate::owner_leak::OwnerLeaker;
 
 use super::{
+    layout::{FieldKind, Layout, Leaf},
+    meta_field::InnerMetaField,
     status_signed::{InnerStatusSign, SignType},
-    StatusSign,
+    ConfigProblem, MetaField, StatusSign,
 };
 
 static SIGNED_STATUS: StatusSign = StatusSign {
@@ -17,6 +19,21 @@ static ERROR_MESSAGE: &str = "error-message";
 static BODY: &str = "body";
 
 /// the config information of serialize
+///
+/// ## Field path
+/// all the field names can be a `.` separated path, the fields sharing the same prefix
+/// will be nested into the same object. For example, the following names
+/// - status sign: `status.ok`
+/// - error message: `status.message`
+/// - body: `data`
+///
+/// will serialize the response like
+/// ```json
+/// {"status": {"ok": true, "message": null}, "data": {}}
+/// ```
+///
+/// the layout is computed once when [`set_config`](crate::set_config),
+/// a field path being the same or the prefix of other field path is invalid
 pub trait SerdeConfig {
     /// the name of body field, the field will be available when the [`RespResult`](crate::RespResult)
     /// is `Success(_)`
@@ -44,6 +61,21 @@ pub trait SerdeConfig {
         true
     }
 
+    /// flatten the body into the response,
+    /// - if return `true`, the fields of body will be serialized at the same level as the
+    ///   status sign and error message instead of nesting under the body field
+    ///
+    /// the fields are written into the envelope directly in their original order.
+    /// the body that not serialized into a map or struct (e.g. number, sequence or
+    /// externally tagged enum) will fall back to nesting under the body field.
+    /// A body field colliding with other envelope field is skipped with a warning
+    ///
+    /// ## Default
+    /// the default value is false
+    fn flatten_body(&self) -> bool {
+        false
+    }
+
     /// sign the status of response
     /// - Some(_) **enable** this sign
     /// - None **disable** this sign
@@ -58,8 +90,7 @@ pub trait SerdeConfig {
 
     /// extra error message
     /// - Some(_) **enable** extra error message
-    ///- None **disable** extra error message
-    /// extra-error
+    /// - None **disable** extra error message
     ///
     /// ## Default
     /// default enable with field name `extra-msg`
@@ -67,69 +98,87 @@ pub trait SerdeConfig {
     fn extra_message(&self) -> Option<Cow<'static, str>> {
         Some(EXTRA_ERR_MESSAGE.into())
     }
-}
-
-pub(crate) struct InnerSerdeConfig {
-    pub(crate) body_name: &'static str,
-    pub(crate) err_msg_name: &'static str,
-    pub(crate) full_field: bool,
-    pub(crate) signed_status: Option<InnerStatusSign>,
-    #[cfg(feature = "extra-error")]
-    pub(crate) extra_code: Option<&'static str>,
-    pub(crate) field_size: FieldSize,
-}
-
-impl InnerSerdeConfig {
-    pub(crate) fn into_inner<C: SerdeConfig>(cfg: &C) -> Self {
-        let mut s = Self {
-            body_name: cfg.body_name().leak(),
-            err_msg_name: cfg.err_msg_name().leak(),
-            full_field: cfg.fixed_field(),
-            signed_status: cfg.signed_status().map(Into::into),
-            #[cfg(feature = "extra-error")]
-            extra_code: cfg.extra_message().leak(),
-            field_size: Default::default(),
-        };
 
-        let f = FieldSize::new(&s);
-        s.field_size = f;
-
-        s
+    /// the field-level details of error, see [`RespError::details`](crate::RespError::details)
+    /// - Some(_) **enable** the details field
+    /// - None **disable** the details field
+    ///
+    /// ## Default
+    /// default disable
+    fn details_name(&self) -> Option<Cow<'static, str>> {
+        None
     }
 
-    pub(crate) fn get_field_size(&self) -> (usize, usize) {
-        let FieldSize { ok_size, err_size } = self.field_size;
-        (ok_size, err_size)
+    /// extra top-level fields of the response, the value of each field is
+    /// read from the [`RespContext`](crate::RespContext) of current request.
+    /// these fields will be available in both `Success(_)` and `Err(_)`
+    ///
+    /// > note: the [`RespContextLayer`](crate::RespContextLayer) (require feature `context`)
+    /// > is needed for providing the context, otherwise all the fields will be `null`
+    ///
+    /// ## Default
+    /// default no extra fields
+    fn meta_fields(&self) -> Vec<MetaField> {
+        Vec::new()
     }
 }
 
-#[derive(Debug, Default)]
-pub(crate) struct FieldSize {
-    ok_size: usize,
-    err_size: usize,
+pub(crate) struct InnerSerdeConfig {
+    pub(crate) flatten_body: bool,
+    pub(crate) signed_status: Option<InnerStatusSign>,
+    pub(crate) meta_fields: Vec<InnerMetaField>,
+    pub(crate) layout: Layout,
 }
 
-impl FieldSize {
-    pub(crate) fn new(cfg: &InnerSerdeConfig) -> Self {
-        let (mut ok_size, mut err_size) = (1, 1);
+impl InnerSerdeConfig {
+    pub(crate) fn into_inner<C: SerdeConfig>(cfg: &C) -> Result<Self, Vec<ConfigProblem>> {
+        let full_field = cfg.fixed_field();
+        let flatten_body = cfg.flatten_body();
+        let signed_status: Option<InnerStatusSign> = cfg.signed_status().map(Into::into);
+        let meta_fields: Vec<InnerMetaField> =
+            cfg.meta_fields().into_iter().map(Into::into).collect();
+
+        let mut fields = Vec::with_capacity(5 + meta_fields.len());
         // 简易状态标记
-        if cfg.signed_status.is_some() {
-            ok_size += 1;
-            err_size += 1;
+        if let Some(ref sign) = signed_status {
+            fields.push((sign.field, Leaf::new(FieldKind::Sign, true, true)));
         }
         //额外的异常码
         #[cfg(feature = "extra-error")]
-        if cfg.extra_code.is_some() {
-            if cfg.full_field {
-                ok_size += 1;
-            }
-            err_size += 1;
+        if let Some(extra_code) = cfg.extra_message().leak() {
+            fields.push((
+                extra_code,
+                Leaf::new(FieldKind::ExtraMessage, full_field, true),
+            ));
         }
-
-        if cfg.full_field {
-            ok_size += 1;
-            err_size += 1;
+        fields.push((
+            cfg.err_msg_name().leak(),
+            Leaf::new(FieldKind::ErrMessage, full_field, true),
+        ));
+        // 字段级错误详情
+        if let Some(details) = cfg.details_name().leak() {
+            fields.push((details, Leaf::new(FieldKind::Details, full_field, true)));
         }
-        Self { ok_size, err_size }
+        // 展开响应体时异常响应不包含响应体字段
+        fields.push((
+            cfg.body_name().leak(),
+            Leaf::new(FieldKind::Body, true, full_field && !flatten_body),
+        ));
+        // 请求上下文字段
+        fields.extend(
+            meta_fields
+                .iter()
+                .enumerate()
+                .map(|(idx, meta)| (meta.field, Leaf::new(FieldKind::Meta(idx), true, true))),
+        );
+
+        let layout = Layout::build(fields)?;
+
+        Ok(Self {
+            flatten_body,
+            signed_status,
+            meta_fields,
+            layout,
+        })
     }
 }
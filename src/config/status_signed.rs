@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use serde::Serialize;
 
-use crate::owner_leak::OwnerLeaker;
+use crate::{owner_leak::OwnerLeaker, RespError};
 
 #[derive(Debug, Clone)]
 /// the full info of status sign
@@ -36,7 +36,7 @@ pub enum SignType {
     /// using the provide number as the sign
     /// - `Success` => `on_ok`
     /// - `Err` => `on_fail`
-    Number { on_ok: u8, on_fail: u8 },
+    Number { on_ok: i64, on_fail: i64 },
     /// using the provide string as the sign
     /// - `Success` => `on_ok`
     /// - `Err` => `on_fail`
@@ -44,6 +44,19 @@ pub enum SignType {
         on_ok: Cow<'static, str>,
         on_fail: Cow<'static, str>,
     },
+    /// using the code of the error as the sign
+    /// - `Success` => `on_ok`
+    /// - `Err` => the code of the error, decided by `source`
+    ErrorCode { on_ok: i64, source: CodeSource },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// where the code of the error come from
+pub enum CodeSource {
+    /// using [`RespError::http_code`]
+    HttpCode,
+    /// using [`RespError::business_code`]
+    BusinessCode,
 }
 
 impl SignType {
@@ -58,12 +71,18 @@ impl SignType {
     }
 
     /// create a [`SignType`] using `Number`
-    pub const fn new_number(ok: u8, err: u8) -> Self {
+    pub const fn new_number(ok: i64, err: i64) -> Self {
         Self::Number {
             on_ok: ok,
             on_fail: err,
         }
     }
+
+    /// create a [`SignType`] using `Number`, accept any integer convertible into `i64`
+    pub fn new_number_from(ok: impl Into<i64>, err: impl Into<i64>) -> Self {
+        Self::new_number(ok.into(), err.into())
+    }
+
     /// create a [`SignType`] using `Str`
     pub fn new_str(ok: impl Into<Cow<'static, str>>, err: impl Into<Cow<'static, str>>) -> Self {
         Self::Str {
@@ -71,6 +90,22 @@ impl SignType {
             on_fail: err.into(),
         }
     }
+
+    /// create a [`SignType`] using `ErrorCode` with [`CodeSource::HttpCode`]
+    pub fn new_http_code(ok: impl Into<i64>) -> Self {
+        Self::ErrorCode {
+            on_ok: ok.into(),
+            source: CodeSource::HttpCode,
+        }
+    }
+
+    /// create a [`SignType`] using `ErrorCode` with [`CodeSource::BusinessCode`]
+    pub fn new_business_code(ok: impl Into<i64>) -> Self {
+        Self::ErrorCode {
+            on_ok: ok.into(),
+            source: CodeSource::BusinessCode,
+        }
+    }
 }
 
 pub(crate) struct InnerStatusSign {
@@ -88,6 +123,7 @@ impl From<StatusSign> for InnerStatusSign {
                 (StatusEnum::Number(on_ok), StatusEnum::Number(on_fail))
             }
             SignType::Str { on_ok, on_fail } => (StatusEnum::Str(on_ok), StatusEnum::Str(on_fail)),
+            SignType::ErrorCode { on_ok, source } => (StatusEnum::Number(on_ok), source.into()),
         };
 
         Self {
@@ -101,21 +137,48 @@ impl From<StatusSign> for InnerStatusSign {
 pub(crate) enum StatusEnum {
     Bool,
     BoolRev,
-    Number(u8),
+    Number(i64),
     Str(Cow<'static, str>),
+    HttpCode,
+    BusinessCode,
+}
+
+impl From<CodeSource> for StatusEnum {
+    fn from(source: CodeSource) -> Self {
+        match source {
+            CodeSource::HttpCode => StatusEnum::HttpCode,
+            CodeSource::BusinessCode => StatusEnum::BusinessCode,
+        }
+    }
+}
+
+impl StatusEnum {
+    /// the sign value, the `err` is needed when the sign is decided by the error
+    #[inline]
+    pub(crate) fn value<'s, E>(&'s self, err: Option<&'s E>) -> SignValue<'s, E> {
+        SignValue { sign: self, err }
+    }
+}
+
+pub(crate) struct SignValue<'s, E> {
+    sign: &'s StatusEnum,
+    err: Option<&'s E>,
 }
 
-impl Serialize for StatusEnum {
+impl<E: RespError> Serialize for SignValue<'_, E> {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        match self {
-            StatusEnum::Bool => serializer.serialize_bool(true),
-            StatusEnum::BoolRev => serializer.serialize_bool(false),
-            StatusEnum::Number(num) => serializer.serialize_u8(*num),
-            StatusEnum::Str(s) => serializer.serialize_str(s),
+        match (self.sign, self.err) {
+            (StatusEnum::Bool, _) => serializer.serialize_bool(true),
+            (StatusEnum::BoolRev, _) => serializer.serialize_bool(false),
+            (StatusEnum::Number(num), _) => serializer.serialize_i64(*num),
+            (StatusEnum::Str(s), _) => serializer.serialize_str(s),
+            (StatusEnum::HttpCode, Some(err)) => serializer.serialize_u16(err.http_code().as_u16()),
+            (StatusEnum::BusinessCode, Some(err)) => serializer.serialize_i64(err.business_code()),
+            (StatusEnum::HttpCode | StatusEnum::BusinessCode, None) => serializer.serialize_none(),
         }
     }
 }
@@ -1,6 +1,8 @@
+use std::convert::Infallible;
+
 use axum::extract::{FromRequest, FromRequestParts, Request};
 
-use super::{FromRequestFamily, MapReject, ToInner};
+use super::{FromRequestFamily, MapReject, ToInner, TryMapReject};
 use crate::{Nil, RespError, RespResult};
 
 impl<S, T, E> FromRequest<S> for MapReject<T, E>
@@ -16,7 +18,11 @@ where
         req: Request, state: &S,
     ) -> Result<Self, Self::Rejection> {
         match <T::Payload as FromRequest<S>>::from_request(req, state).await {
-            Ok(data) => Ok(Self(data.to_inner())),
+            Ok(data) => {
+                let inner = data.to_inner();
+                T::validate(&inner).map_err(RespResult::Err)?;
+                Ok(Self(inner))
+            }
             Err(err) => Err(RespResult::Err(E::from(err))),
         }
     }
@@ -41,13 +47,75 @@ where
         )
         .await
         {
-            Ok(data) => Ok(Self(data.to_inner())),
+            Ok(data) => {
+                let inner = data.to_inner();
+                T::validate(&inner).map_err(RespResult::Err)?;
+                Ok(Self(inner))
+            }
             Err(err) => Err(RespResult::Err(E::from(err))),
         }
     }
 }
+
+impl<S, T, E> FromRequest<S> for TryMapReject<T, E>
+where
+    S: Sync + Send,
+    E: Send + From<<T::Payload as FromRequest<S>>::Rejection> + RespError,
+    T: FromRequestFamily<E>,
+    T::Payload: FromRequest<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let result = match <T::Payload as FromRequest<S>>::from_request(req, state).await {
+            Ok(data) => {
+                let inner = data.to_inner();
+                T::validate(&inner).map(|_| inner)
+            }
+            Err(err) => Err(E::from(err)),
+        };
+        Ok(Self(result))
+    }
+}
+
+impl<S, T, E> FromRequestParts<S> for TryMapReject<T, E>
+where
+    S: Sync + Send,
+    E: Send + From<<T::Payload as FromRequestParts<S>>::Rejection> + RespError,
+    T: FromRequestFamily<E>,
+    T::Payload: FromRequestParts<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let result =
+            match <T::Payload as FromRequestParts<S>>::from_request_parts(parts, state).await {
+                Ok(data) => {
+                    let inner = data.to_inner();
+                    T::validate(&inner).map(|_| inner)
+                }
+                Err(err) => Err(E::from(err)),
+            };
+        Ok(Self(result))
+    }
+}
+
 mod from_request_families {
-    use axum::extract::{Extension, Form, Json, Path, Query, State};
+    #[cfg(feature = "multipart")]
+    use axum::extract::Multipart;
+    #[cfg(feature = "ws")]
+    use axum::extract::WebSocketUpgrade;
+    use axum::{
+        body::Bytes,
+        extract::{
+            ConnectInfo, Extension, Form, Json, MatchedPath, OriginalUri, Path, Query, RawForm,
+            State,
+        },
+    };
+    use http::Uri;
 
     use crate::convert::from_request::ToInner;
 
@@ -86,4 +154,287 @@ mod from_request_families {
 
         fn to_inner(self) -> Self::Inner { self.0 }
     }
+
+    impl ToInner for RawForm {
+        type Inner = Bytes;
+
+        fn to_inner(self) -> Self::Inner {
+            self.0
+        }
+    }
+
+    impl ToInner for OriginalUri {
+        type Inner = Uri;
+
+        fn to_inner(self) -> Self::Inner {
+            self.0
+        }
+    }
+
+    impl ToInner for MatchedPath {
+        type Inner = Self;
+
+        fn to_inner(self) -> Self::Inner {
+            self
+        }
+    }
+
+    impl<T> ToInner for ConnectInfo<T> {
+        type Inner = T;
+
+        fn to_inner(self) -> Self::Inner {
+            self.0
+        }
+    }
+
+    #[cfg(feature = "multipart")]
+    impl ToInner for Multipart {
+        type Inner = Self;
+
+        fn to_inner(self) -> Self::Inner {
+            self
+        }
+    }
+
+    #[cfg(feature = "ws")]
+    impl ToInner for WebSocketUpgrade {
+        type Inner = Self;
+
+        fn to_inner(self) -> Self::Inner {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use axum::{
+        body::Body,
+        extract::{
+            rejection::{
+                ExtensionRejection, JsonRejection, MatchedPathRejection, RawFormRejection,
+            },
+            ConnectInfo, FromRequest, FromRequestParts, MatchedPath, OriginalUri, RawForm, Request,
+        },
+        routing::get,
+        Json, Router,
+    };
+    use http::StatusCode;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use crate::{
+        convert::from_request::test_util::{reject_from, RejectError},
+        MapReject, RespError, RespResult, TryMapReject,
+    };
+
+    #[derive(Debug)]
+    struct JsonError(StatusCode);
+
+    impl RespError for JsonError {
+        fn log_message(&self) -> std::borrow::Cow<'_, str> {
+            "Json Error".into()
+        }
+
+        fn http_code(&self) -> StatusCode {
+            self.0
+        }
+
+        #[cfg(feature = "extra-error")]
+        type ExtraMessage = String;
+
+        #[cfg(feature = "extra-error")]
+        fn extra_message(&self) -> Self::ExtraMessage {
+            String::new()
+        }
+    }
+
+    impl From<JsonRejection> for JsonError {
+        fn from(err: JsonRejection) -> Self {
+            Self(err.status())
+        }
+    }
+
+    reject_from!(RawFormRejection, MatchedPathRejection, ExtensionRejection,);
+    #[cfg(feature = "multipart")]
+    reject_from!(axum::extract::multipart::MultipartRejection);
+    #[cfg(feature = "ws")]
+    reject_from!(axum::extract::ws::rejection::WebSocketUpgradeRejection);
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: u32,
+    }
+
+    fn request(content_type: Option<&str>, body: &'static str) -> Request {
+        let mut builder = Request::builder();
+        if let Some(content_type) = content_type {
+            builder = builder.header("content-type", content_type);
+        }
+        builder.body(body.into()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_optional() {
+        type Extractor = MapReject<Option<Json<User>>, JsonError>;
+
+        let MapReject(user) = Extractor::from_request(request(None, ""), &())
+            .await
+            .unwrap();
+        assert_eq!(user, None);
+
+        let MapReject(user) =
+            Extractor::from_request(request(Some("application/json"), r#"{"id": 1}"#), &())
+                .await
+                .unwrap();
+        assert_eq!(user, Some(User { id: 1 }));
+
+        let Err(RespResult::Err(err)) =
+            Extractor::from_request(request(Some("application/json"), "{"), &()).await
+        else {
+            panic!("expect json rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_try_map_reject() {
+        type Extractor = TryMapReject<Json<User>, JsonError>;
+
+        let Ok(TryMapReject(user)) =
+            Extractor::from_request(request(Some("application/json"), r#"{"id": 1}"#), &()).await;
+        assert_eq!(user.unwrap(), User { id: 1 });
+
+        let Ok(TryMapReject(user)) =
+            Extractor::from_request(request(Some("text/plain"), r#"{"id": 1}"#), &()).await;
+        assert_eq!(
+            user.unwrap_err().http_code(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_meta() {
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/users/1")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from("name=foo"))
+            .unwrap();
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+        req.extensions_mut().insert(ConnectInfo(addr));
+        let (mut parts, body) = req.into_parts();
+
+        let MapReject(uri) =
+            MapReject::<OriginalUri, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(uri.path(), "/users/1");
+
+        let MapReject(remote) =
+            MapReject::<ConnectInfo<SocketAddr>, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(remote, addr);
+
+        // not inside a router
+        let Err(RespResult::Err(err)) =
+            MapReject::<MatchedPath, RejectError>::from_request_parts(&mut parts, &()).await
+        else {
+            panic!("expect matched path rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let MapReject(form) =
+            MapReject::<RawForm, RejectError>::from_request(Request::from_parts(parts, body), &())
+                .await
+                .unwrap();
+        assert_eq!(form, "name=foo");
+    }
+
+    #[tokio::test]
+    async fn test_matched_path() {
+        let router = Router::new().route(
+            "/users/{id}",
+            get(
+                |MapReject(path): MapReject<MatchedPath, RejectError>| async move {
+                    path.as_str().to_owned()
+                },
+            ),
+        );
+
+        let resp = router
+            .oneshot(Request::get("/users/1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "/users/{id}");
+    }
+
+    #[cfg(feature = "multipart")]
+    #[tokio::test]
+    async fn test_multipart() {
+        use axum::extract::Multipart;
+
+        let req = Request::builder()
+            .method("POST")
+            .header("content-type", "multipart/form-data; boundary=X")
+            .body(Body::from(
+                "--X\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nfoo\r\n--X--\r\n",
+            ))
+            .unwrap();
+
+        let MapReject(mut multipart) = MapReject::<Multipart, RejectError>::from_request(req, &())
+            .await
+            .unwrap();
+        let field = multipart.next_field().await.unwrap().unwrap();
+        assert_eq!(field.name(), Some("name"));
+        assert_eq!(field.text().await.unwrap(), "foo");
+
+        let Err(RespResult::Err(err)) =
+            MapReject::<Multipart, RejectError>::from_request(request(None, ""), &()).await
+        else {
+            panic!("expect multipart rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn test_ws_upgrade() {
+        use axum::extract::WebSocketUpgrade;
+
+        let upgrade_request = || {
+            Request::get("/ws")
+                .header("connection", "upgrade")
+                .header("upgrade", "websocket")
+                .header("sec-websocket-version", "13")
+                .header("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let mut req = upgrade_request();
+        let on_upgrade = hyper::upgrade::on(Request::new(()));
+        req.extensions_mut().insert(on_upgrade);
+        let (mut parts, _) = req.into_parts();
+        assert!(
+            MapReject::<WebSocketUpgrade, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .is_ok()
+        );
+
+        // the connection not served by hyper
+        let (mut parts, _) = upgrade_request().into_parts();
+        let Err(RespResult::Err(err)) =
+            MapReject::<WebSocketUpgrade, RejectError>::from_request_parts(&mut parts, &()).await
+        else {
+            panic!("expect upgrade rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::UPGRADE_REQUIRED);
+    }
 }
@@ -0,0 +1,236 @@
+#[cfg(feature = "cookie")]
+use axum_extra::extract::CookieJar;
+use axum_extra::extract::{Cached, Host};
+#[cfg(feature = "extra-query")]
+use axum_extra::extract::{OptionalQuery, Query};
+#[cfg(feature = "protobuf")]
+use axum_extra::protobuf::Protobuf;
+#[cfg(feature = "typed-header")]
+use axum_extra::TypedHeader;
+
+use super::ToInner;
+
+impl<T> ToInner for Cached<T> {
+    type Inner = T;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+impl ToInner for Host {
+    type Inner = String;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+#[cfg(feature = "typed-header")]
+impl<T> ToInner for TypedHeader<T> {
+    type Inner = T;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+#[cfg(feature = "extra-query")]
+impl<T> ToInner for Query<T> {
+    type Inner = T;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+/// missing query string will be `None`, malformed query string still reject the request
+#[cfg(feature = "extra-query")]
+impl<T> ToInner for OptionalQuery<T> {
+    type Inner = Option<T>;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+#[cfg(feature = "cookie")]
+impl ToInner for CookieJar {
+    type Inner = Self;
+
+    fn to_inner(self) -> Self::Inner {
+        self
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<T> ToInner for Protobuf<T> {
+    type Inner = T;
+
+    fn to_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::extract::{FromRequestParts, Request};
+    use axum_extra::extract::{rejection::HostRejection, Cached, Host};
+    use http::StatusCode;
+
+    use crate::{
+        convert::from_request::test_util::{reject_from, RejectError},
+        MapReject, RespError, RespResult,
+    };
+
+    reject_from!(HostRejection);
+    #[cfg(feature = "typed-header")]
+    reject_from!(axum_extra::typed_header::TypedHeaderRejection);
+    #[cfg(feature = "extra-query")]
+    reject_from!(
+        axum_extra::extract::QueryRejection,
+        axum_extra::extract::OptionalQueryRejection,
+    );
+    #[cfg(feature = "protobuf")]
+    reject_from!(axum_extra::protobuf::ProtobufRejection);
+
+    fn parts(uri: &str, headers: &[(&str, &str)]) -> http::request::Parts {
+        let mut builder = Request::get(uri);
+        for (key, value) in headers {
+            builder = builder.header(*key, *value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[tokio::test]
+    async fn test_host() {
+        let mut parts = parts("/", &[("host", "example.com")]);
+
+        let MapReject(host) = MapReject::<Host, RejectError>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(host, "example.com");
+
+        let MapReject(Host(host)) =
+            MapReject::<Cached<Host>, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(host, "example.com");
+
+        let Err(RespResult::Err(err)) =
+            MapReject::<Host, RejectError>::from_request_parts(&mut self::parts("/", &[]), &())
+                .await
+        else {
+            panic!("expect host rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "typed-header")]
+    #[tokio::test]
+    async fn test_typed_header() {
+        use axum_extra::{headers::UserAgent, TypedHeader};
+
+        let mut parts = parts("/", &[("user-agent", "curl/8.0")]);
+        let MapReject(agent) =
+            MapReject::<TypedHeader<UserAgent>, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(agent.as_str(), "curl/8.0");
+
+        let Err(RespResult::Err(err)) =
+            MapReject::<TypedHeader<UserAgent>, RejectError>::from_request_parts(
+                &mut self::parts("/", &[]),
+                &(),
+            )
+            .await
+        else {
+            panic!("expect typed header rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "extra-query")]
+    #[tokio::test]
+    async fn test_query() {
+        use axum_extra::extract::{OptionalQuery, Query};
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Ids {
+            id: Vec<u32>,
+        }
+
+        let mut parts = parts("/?id=1&id=2", &[]);
+        let MapReject(ids) =
+            MapReject::<Query<Ids>, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(ids, Ids { id: vec![1, 2] });
+
+        let MapReject(ids) = MapReject::<OptionalQuery<Ids>, RejectError>::from_request_parts(
+            &mut self::parts("/", &[]),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids, None);
+
+        let Err(RespResult::Err(err)) = MapReject::<Query<Ids>, RejectError>::from_request_parts(
+            &mut self::parts("/?id=a", &[]),
+            &(),
+        )
+        .await
+        else {
+            panic!("expect query rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "cookie")]
+    #[tokio::test]
+    async fn test_cookie() {
+        use axum_extra::extract::CookieJar;
+
+        let mut parts = parts("/", &[("cookie", "session=abc; theme=dark")]);
+        let MapReject(jar) =
+            MapReject::<CookieJar, RejectError>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(jar.get("session").unwrap().value(), "abc");
+        assert_eq!(jar.get("theme").unwrap().value(), "dark");
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[tokio::test]
+    async fn test_protobuf() {
+        use axum::{body::Body, extract::FromRequest};
+        use axum_extra::protobuf::Protobuf;
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, Message)]
+        struct Ping {
+            #[prost(uint32, tag = "1")]
+            id: u32,
+        }
+
+        let req = Request::builder()
+            .method("POST")
+            .body(Body::from(Ping { id: 1 }.encode_to_vec()))
+            .unwrap();
+        let MapReject(ping) = MapReject::<Protobuf<Ping>, RejectError>::from_request(req, &())
+            .await
+            .unwrap();
+        assert_eq!(ping, Ping { id: 1 });
+
+        let req = Request::builder()
+            .method("POST")
+            .body(Body::from(vec![0xff]))
+            .unwrap();
+        let Err(RespResult::Err(err)) =
+            MapReject::<Protobuf<Ping>, RejectError>::from_request(req, &()).await
+        else {
+            panic!("expect protobuf rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{Nil, RespResult};
+
+/// merge the rejections of the fields, used by the derived
+/// [`RespFromRequestParts`](crate::RespFromRequestParts) and
+/// [`RespFromRequest`](crate::RespFromRequest) for reporting all the failed fields at once
+///
+/// [`SimpleRespError`](crate::SimpleRespError), [`DynRespError`](crate::DynRespError) and
+/// [`RejectionError`](crate::RejectionError) collect the details of all the fields, see
+/// [`SimpleRespError`](crate::SimpleRespError) for the merging rule. The errors derived by
+/// [`RespError`](macro@crate::RespError) with `#[resp_result(merge_first)]` keep the first
+/// rejection, the other errors can implement it with an empty impl block for the same behavior
+pub trait MergeRejection: Sized {
+    /// merge the rejection of later field into the former one
+    ///
+    /// ## Default
+    /// default keep the former one, the later one is dropped
+    #[inline]
+    #[allow(unused_variables)]
+    fn merge(self, other: Self) -> Self {
+        self
+    }
+}
+
+/// record the rejection of a field
+#[doc(hidden)]
+pub fn merge_rejection<E: MergeRejection>(rejection: &mut Option<E>, err: E) {
+    *rejection = Some(match rejection.take() {
+        Some(prev) => prev.merge(err),
+        None => err,
+    });
+}
+
+/// take the error out of the rejection of [`MapReject`](crate::MapReject)
+#[doc(hidden)]
+pub fn take_rejection<E>(rejection: RespResult<Nil, E>) -> E {
+    match rejection {
+        RespResult::Err(err) => err,
+        RespResult::Success(_) => unreachable!("`MapReject` never rejects with `Success`"),
+    }
+}
+
+#[cfg(all(test, feature = "rejection"))]
+mod test {
+    use axum::{
+        body::Body,
+        extract::{FromRequest, Path, Query, Request},
+        routing::post,
+        Json, Router,
+    };
+    use http::StatusCode;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use crate::{RejectionError, RespError, RespResult, SimpleRespError};
+
+    #[derive(Deserialize)]
+    struct Page {
+        page: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(crate::RespFromRequest)]
+    #[resp_result(rejection(SimpleRespError))]
+    struct CreateUser {
+        #[resp_result(via(Path))]
+        id: u32,
+        #[resp_result(via(Query))]
+        page: Page,
+        #[resp_result(body, via(Json))]
+        user: User,
+    }
+
+    fn request(uri: &str, body: &'static str) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_derive_merge() {
+        // 没有路由，路径参数缺失为服务端错误
+        let Err(RespResult::Err(err)) =
+            CreateUser::from_request(request("/?page=abc", r#"{"name": 1}"#), &()).await
+        else {
+            panic!("expect rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.resp_message(), "Internal Server Error");
+        let details = err
+            .details()
+            .unwrap()
+            .into_iter()
+            .map(|detail| (detail.field, detail.code))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            details,
+            [
+                ("page".into(), "query".into()),
+                ("name".into(), "json_data".into())
+            ]
+        );
+
+        async fn create(user: CreateUser) -> String {
+            format!("{}-{}-{}", user.id, user.page.page, user.user.name)
+        }
+        let router = Router::new().route("/users/{id}", post(create));
+
+        let resp = router
+            .clone()
+            .oneshot(request("/users/1?page=abc", r#"{"name": 1}"#))
+            .await
+            .unwrap();
+        // the first client error decides the status code
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = router
+            .oneshot(request("/users/1?page=2", r#"{"name": "foo"}"#))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "1-2-foo");
+    }
+
+    #[derive(crate::RespFromRequestParts)]
+    #[resp_result(rejection(RejectionError))]
+    struct ListUsers {
+        #[resp_result(via(Path))]
+        id: u32,
+        #[resp_result(via(Query))]
+        page: Page,
+    }
+
+    fn get(uri: &str) -> Request {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_derive_rejection_error() {
+        use axum::extract::FromRequestParts;
+
+        // 没有路由，路径参数缺失为服务端错误
+        let (mut parts, _) = get("/?page=abc").into_parts();
+        let Err(RespResult::Err(err)) = ListUsers::from_request_parts(&mut parts, &()).await else {
+            panic!("expect rejection")
+        };
+        assert_eq!(err.code(), "path_missing");
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        let details = err
+            .details()
+            .unwrap()
+            .into_iter()
+            .map(|detail| (detail.field, detail.code))
+            .collect::<Vec<_>>();
+        assert_eq!(details, [("page".into(), "query".into())]);
+
+        async fn list(users: ListUsers) -> String {
+            format!("{}-{}", users.id, users.page.page)
+        }
+        let router = Router::new().route("/users/{id}", axum::routing::get(list));
+        let resp = router.oneshot(get("/users/1?page=2")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_derive_infallible() {
+        use axum::extract::State;
+        use http::HeaderMap;
+
+        #[derive(Clone)]
+        struct AppState {
+            name: &'static str,
+        }
+
+        #[derive(crate::RespFromRequestParts)]
+        #[resp_result(rejection(SimpleRespError))]
+        struct UserContext {
+            #[resp_result(via(State))]
+            state: AppState,
+            headers: HeaderMap,
+            #[resp_result(via(Path))]
+            id: u32,
+        }
+
+        async fn user(ctx: UserContext) -> String {
+            format!("{}-{:?}-{}", ctx.state.name, ctx.headers["x-user"], ctx.id)
+        }
+        let router = Router::new()
+            .route("/users/{id}", axum::routing::get(user))
+            .with_state(AppState { name: "app" });
+
+        let req = Request::builder()
+            .uri("/users/1")
+            .header("x-user", "foo")
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "app-\"foo\"-1");
+
+        let resp = router.oneshot(get("/users/abc")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(not(feature = "extra-error"))]
+    #[tokio::test]
+    async fn test_derive_merge_first() {
+        use axum::extract::{
+            rejection::{PathRejection, QueryRejection},
+            FromRequestParts,
+        };
+
+        #[derive(Debug, thiserror::Error, crate::RespError)]
+        #[resp_result(merge_first)]
+        enum ApiError {
+            #[error("bad path")]
+            #[resp_result(err_code = 400)]
+            Path,
+            #[error("bad query")]
+            #[resp_result(err_code = 422)]
+            Query,
+        }
+
+        impl From<PathRejection> for ApiError {
+            fn from(_: PathRejection) -> Self {
+                Self::Path
+            }
+        }
+
+        impl From<QueryRejection> for ApiError {
+            fn from(_: QueryRejection) -> Self {
+                Self::Query
+            }
+        }
+
+        #[derive(crate::RespFromRequestParts)]
+        #[resp_result(rejection(ApiError))]
+        struct Params {
+            #[resp_result(via(Query))]
+            #[allow(dead_code)]
+            page: Page,
+            #[resp_result(via(Path))]
+            #[allow(dead_code)]
+            id: u32,
+        }
+
+        // only the rejection of the first failed field is kept
+        let (mut parts, _) = get("/?page=abc").into_parts();
+        let Err(RespResult::Err(err)) = Params::from_request_parts(&mut parts, &()).await else {
+            panic!("expect rejection")
+        };
+        assert!(matches!(err, ApiError::Query));
+        assert_eq!(err.http_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -1,4 +1,16 @@
 mod axum;
+#[cfg(feature = "axum-extra")]
+mod axum_extra;
+mod merge;
+#[cfg(any(feature = "validator", feature = "garde"))]
+pub mod valid;
+
+#[cfg(test)]
+mod test_util;
+
+pub use merge::MergeRejection;
+#[doc(hidden)]
+pub use merge::{merge_rejection, take_rejection};
 pub trait ToInner {
     type Inner;
     fn to_inner(self) -> Self::Inner;
@@ -6,6 +18,16 @@ pub trait ToInner {
 
 pub trait FromRequestFamily<E> {
     type Payload: ToInner;
+
+    /// check the inner value after extracted, reject with `E` if failure
+    ///
+    /// ## Default
+    /// default accept all the value
+    #[inline]
+    #[allow(unused_variables)]
+    fn validate(inner: &<Self::Payload as ToInner>::Inner) -> Result<(), E> {
+        Ok(())
+    }
 }
 
 impl<E, F> FromRequestFamily<E> for F
@@ -15,4 +37,23 @@ where
     type Payload = Self;
 }
 
+/// missing value will be `None`, malformed value still reject the request,
+/// e.g. `Option<Json<T>>`, `Option<Path<T>>` and `Option<TypedHeader<T>>`,
+/// see [`OptionalFromRequest`](axum::extract::OptionalFromRequest) and
+/// [`OptionalFromRequestParts`](axum::extract::OptionalFromRequestParts) for the extractors supported
+impl<T> ToInner for Option<T>
+where
+    T: ToInner,
+{
+    type Inner = Option<T::Inner>;
+
+    fn to_inner(self) -> Self::Inner {
+        self.map(ToInner::to_inner)
+    }
+}
+
 pub struct MapReject<T: FromRequestFamily<E>, E>(pub <T::Payload as ToInner>::Inner);
+
+/// like [`MapReject`], but the failure of extracting or validating is passed to
+/// the handler as `Err(E)` instead of rejecting the request
+pub struct TryMapReject<T: FromRequestFamily<E>, E>(pub Result<<T::Payload as ToInner>::Inner, E>);
@@ -0,0 +1,44 @@
+use std::{borrow::Cow, convert::Infallible};
+
+use http::StatusCode;
+
+use crate::RespError;
+
+/// the error keeping the status code of the rejection
+#[derive(Debug)]
+pub(super) struct RejectError(pub(super) StatusCode);
+
+impl RespError for RejectError {
+    fn log_message(&self) -> Cow<'_, str> {
+        "Reject Error".into()
+    }
+
+    fn http_code(&self) -> StatusCode {
+        self.0
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = String;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        String::new()
+    }
+}
+
+/// implement `From` of the rejections for [`RejectError`], keeping the status code
+macro_rules! reject_from {
+    ($($rejection:ty),* $(,)?) => {
+        $(
+            impl From<$rejection> for $crate::convert::from_request::test_util::RejectError {
+                fn from(err: $rejection) -> Self {
+                    Self(::axum::response::IntoResponse::into_response(err).status())
+                }
+            }
+        )*
+    };
+}
+
+pub(super) use reject_from;
+
+reject_from!(Infallible);
@@ -0,0 +1,295 @@
+use std::marker::PhantomData;
+
+use axum::extract::{Form, Json, Path, Query};
+
+use super::FromRequestFamily;
+
+/// the validation backend of the validated extractors
+pub trait ValidBackend<T> {
+    /// the validation report, the error of handler need to implement `From<Report>`
+    type Report;
+
+    fn validate(value: &T) -> Result<(), Self::Report>;
+}
+
+/// validate by [`validator::Validate`]
+#[cfg(feature = "validator")]
+#[cfg_attr(docsrs, doc(cfg(feature = "validator")))]
+pub struct ValidatorBackend;
+
+#[cfg(feature = "validator")]
+impl<T> ValidBackend<T> for ValidatorBackend
+where
+    T: validator::Validate,
+{
+    type Report = validator::ValidationErrors;
+
+    fn validate(value: &T) -> Result<(), Self::Report> {
+        value.validate()
+    }
+}
+
+/// validate by [`garde::Validate`] with the default context
+#[cfg(feature = "garde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "garde")))]
+pub struct GardeBackend;
+
+#[cfg(feature = "garde")]
+impl<T> ValidBackend<T> for GardeBackend
+where
+    T: garde::Validate,
+    T::Context: Default,
+{
+    type Report = garde::Report;
+
+    fn validate(value: &T) -> Result<(), Self::Report> {
+        value.validate_with(&Default::default())
+    }
+}
+
+macro_rules! valid_family {
+    ($(#[$meta:meta])* $name:ident, $payload:ident) => {
+        $(#[$meta])*
+        ///
+        /// only used as the type param of [`MapReject`](crate::MapReject), both the rejection
+        /// and the validation report will be converted into the error by [`From`].
+        /// the backend `B` is required, e.g. [`ValidatorBackend`] or [`GardeBackend`]
+        pub struct $name<T, B>(PhantomData<fn() -> (T, B)>);
+
+        impl<T, B, E> FromRequestFamily<E> for $name<T, B>
+        where
+            B: ValidBackend<T>,
+            E: From<B::Report>,
+        {
+            type Payload = $payload<T>;
+
+            fn validate(inner: &T) -> Result<(), E> {
+                B::validate(inner).map_err(E::from)
+            }
+        }
+    };
+}
+
+valid_family!(
+    /// extract like [`Json`] then validate the payload
+    ValidJson,
+    Json
+);
+valid_family!(
+    /// extract like [`Query`] then validate the payload
+    ValidQuery,
+    Query
+);
+valid_family!(
+    /// extract like [`Form`] then validate the payload
+    ValidForm,
+    Form
+);
+valid_family!(
+    /// extract like [`Path`] then validate the payload
+    ValidPath,
+    Path
+);
+
+macro_rules! backend_alias {
+    ($feature:literal, $backend:ident: $($alias:ident = $name:ident),*) => {
+        $(
+            #[doc = concat!("[`", stringify!($name), "`] validated by [`", stringify!($backend), "`]")]
+            #[cfg(feature = $feature)]
+            #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+            pub type $alias<T> = $name<T, $backend>;
+        )*
+    };
+}
+
+backend_alias!(
+    "validator",
+    ValidatorBackend: ValidatorJson = ValidJson,
+    ValidatorQuery = ValidQuery,
+    ValidatorForm = ValidForm,
+    ValidatorPath = ValidPath
+);
+backend_alias!(
+    "garde",
+    GardeBackend: GardeJson = ValidJson,
+    GardeQuery = ValidQuery,
+    GardeForm = ValidForm,
+    GardePath = ValidPath
+);
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use axum::extract::{rejection::JsonRejection, FromRequest, Request};
+    use http::StatusCode;
+    use serde::Deserialize;
+
+    use crate::{ErrorDetail, MapReject, RespError, RespResult, SimpleRespError};
+
+    /// delegate to `SimpleRespError`, with the conversion of rejection for the tests only
+    #[derive(Debug)]
+    struct MockErr(SimpleRespError);
+
+    impl RespError for MockErr {
+        fn log_message(&self) -> Cow<'_, str> {
+            self.0.log_message()
+        }
+
+        fn http_code(&self) -> StatusCode {
+            self.0.http_code()
+        }
+
+        fn details(&self) -> Option<Vec<ErrorDetail>> {
+            self.0.details()
+        }
+
+        #[cfg(feature = "extra-error")]
+        type ExtraMessage = &'static str;
+
+        #[cfg(feature = "extra-error")]
+        fn extra_message(&self) -> Self::ExtraMessage {
+            "Mock"
+        }
+    }
+
+    impl From<JsonRejection> for MockErr {
+        fn from(err: JsonRejection) -> Self {
+            Self(SimpleRespError::new(err.status(), err.body_text()))
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    impl From<validator::ValidationErrors> for MockErr {
+        fn from(report: validator::ValidationErrors) -> Self {
+            Self(report.into())
+        }
+    }
+
+    #[cfg(feature = "garde")]
+    impl From<garde::Report> for MockErr {
+        fn from(report: garde::Report) -> Self {
+            Self(report.into())
+        }
+    }
+
+    fn json_request(body: &'static str) -> Request {
+        Request::builder()
+            .header("content-type", "application/json")
+            .body(body.into())
+            .unwrap()
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn test_validator() {
+        use validator::Validate;
+
+        use super::ValidJson;
+        use crate::{ValidatorBackend, ValidatorJson};
+
+        #[derive(Debug, Deserialize, Validate)]
+        struct CreateUser {
+            #[validate(length(min = 3))]
+            name: String,
+        }
+        type Extractor = MapReject<ValidJson<CreateUser, ValidatorBackend>, MockErr>;
+        // the alias is the same type
+        let _: fn(MapReject<ValidatorJson<CreateUser>, MockErr>) -> Extractor = |v| v;
+
+        let MapReject(user) = Extractor::from_request(json_request(r#"{"name": "foo"}"#), &())
+            .await
+            .unwrap();
+        assert_eq!(user.name, "foo");
+
+        let Err(RespResult::Err(err)) =
+            Extractor::from_request(json_request(r#"{"name": "a"}"#), &()).await
+        else {
+            panic!("expect validation error")
+        };
+        assert_eq!(err.http_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        let details = err.details().unwrap();
+        assert_eq!(details[0].field, "name");
+        assert_eq!(details[0].code, "length");
+
+        let Err(RespResult::Err(err)) = Extractor::from_request(json_request("{"), &()).await
+        else {
+            panic!("expect json rejection")
+        };
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_validator_nested() {
+        use validator::Validate;
+
+        #[derive(Validate)]
+        struct Address {
+            #[validate(length(min = 2))]
+            city: String,
+        }
+
+        #[derive(Validate)]
+        struct Tag {
+            #[validate(length(max = 3))]
+            name: String,
+        }
+
+        #[derive(Validate)]
+        struct CreateUser {
+            #[validate(length(min = 3))]
+            name: String,
+            #[validate(nested)]
+            address: Address,
+            #[validate(nested)]
+            tags: Vec<Tag>,
+        }
+
+        let user = CreateUser {
+            name: "a".into(),
+            address: Address { city: "x".into() },
+            tags: vec![
+                Tag { name: "ok".into() },
+                Tag {
+                    name: "too long".into(),
+                },
+            ],
+        };
+        let err = SimpleRespError::from(user.validate().unwrap_err());
+        let fields = err
+            .details()
+            .unwrap()
+            .into_iter()
+            .map(|detail| detail.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["address.city", "name", "tags[1].name"]);
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn test_garde() {
+        use crate::GardeJson;
+
+        #[derive(Debug, Deserialize, garde::Validate)]
+        struct CreateUser {
+            #[garde(length(min = 3))]
+            name: String,
+        }
+        type Extractor = MapReject<GardeJson<CreateUser>, MockErr>;
+
+        assert!(
+            Extractor::from_request(json_request(r#"{"name": "foo"}"#), &())
+                .await
+                .is_ok()
+        );
+
+        let Err(RespResult::Err(err)) =
+            Extractor::from_request(json_request(r#"{"name": "a"}"#), &()).await
+        else {
+            panic!("expect validation error")
+        };
+        assert_eq!(err.http_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.details().unwrap()[0].field, "name");
+    }
+}
@@ -1,7 +1,7 @@
 pub mod from_request;
-use std::{convert::Infallible, future::Future};
+use std::{convert::Infallible, future::Future, ops::ControlFlow};
 
-use crate::{RespError, RespResult};
+use crate::{NotFoundError, RespError, RespResult};
 
 /// convert into [`RespResult`](crate::RespResult)
 pub trait IntoRespResult<T, E: RespError> {
@@ -33,13 +33,48 @@ where
     }
 }
 
-impl<T, E> IntoRespResultWithErr<T, E> for Option<T>
+/// `None` will be converted into [`NotFoundError`]
+impl<T, E> IntoRespResult<T, E> for Option<T>
 where
+    E: RespError + From<NotFoundError>,
+{
+    #[inline]
+    fn into_rresult(self) -> RespResult<T, E> {
+        self.ok_or_else(|| NotFoundError.into()).into_rresult()
+    }
+}
+
+/// `Ok(None)` will be converted into [`NotFoundError`]
+impl<T, E> IntoRespResult<T, E> for OrNotFound<Result<Option<T>, E>>
+where
+    E: RespError + From<NotFoundError>,
+{
+    #[inline]
+    fn into_rresult(self) -> RespResult<T, E> {
+        self.to_result().into_rresult()
+    }
+}
+
+/// `Break(_)` will be converted into `Err(_)`
+impl<B, C> IntoRespResult<C, B> for ControlFlow<B, C>
+where
+    B: RespError,
+{
+    #[inline]
+    fn into_rresult(self) -> RespResult<C, B> {
+        self.to_result().into_rresult()
+    }
+}
+
+/// the failure will be replaced by the provided error
+impl<F, E> IntoRespResultWithErr<F::Success, E> for F
+where
+    F: Fallible,
     E: RespError,
 {
     #[inline]
-    fn into_with_err<Et: Into<E>>(self, err: Et) -> RespResult<T, E> {
-        self.ok_or(err).map_err(Into::into).into_rresult()
+    fn into_with_err<Et: Into<E>>(self, err: Et) -> RespResult<F::Success, E> {
+        self.to_result().map_err(|_| err.into()).into_rresult()
     }
 }
 
@@ -73,6 +108,74 @@ impl<T, R> Fallible for RespResult<T, R> {
     }
 }
 
+/// `None` as the failure [`NotFoundError`]
+impl<T> Fallible for Option<T> {
+    type Success = T;
+
+    type Failure = NotFoundError;
+
+    fn to_result(self) -> Result<Self::Success, Self::Failure> {
+        self.ok_or(NotFoundError)
+    }
+}
+
+/// the adapter of `Result<Option<T>, E>`, `Ok(None)` as the failure [`NotFoundError`]
+///
+/// the handler returning `Result<Option<T>, E>` will be wrapped with `#[rresult(not_found)]`,
+/// without the arg `Ok(None)` is kept as the success `None`
+///
+/// ```rust
+/// use axum_resp_result::{rresult, IntoRespResult, NotFoundError, OrNotFound, RespError, RespResult};
+///
+/// #[rresult(not_found)]
+/// fn find(id: u32) -> Result<Option<u32>, NotFoundError> {
+///     Ok((id == 1).then_some(id))
+/// }
+///
+/// assert!(matches!(find(1), RespResult::Success(1)));
+/// let RespResult::Err(err) = find(2) else { panic!("expect not found") };
+/// assert_eq!(err.http_code(), 404);
+///
+/// #[rresult]
+/// fn find_or_null(id: u32) -> Result<Option<u32>, NotFoundError> {
+///     Ok((id == 1).then_some(id))
+/// }
+///
+/// assert!(matches!(find_or_null(2), RespResult::Success(None)));
+///
+/// let resp: RespResult<u32, NotFoundError> = OrNotFound(Ok(None)).into_rresult();
+/// assert!(matches!(resp, RespResult::Err(_)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrNotFound<R>(pub R);
+
+impl<T, E> Fallible for OrNotFound<Result<Option<T>, E>>
+where
+    E: From<NotFoundError>,
+{
+    type Success = T;
+
+    type Failure = E;
+
+    fn to_result(self) -> Result<Self::Success, Self::Failure> {
+        self.0.and_then(|v| v.ok_or_else(|| NotFoundError.into()))
+    }
+}
+
+/// `Continue(_)` as success and `Break(_)` as failure
+impl<B, C> Fallible for ControlFlow<B, C> {
+    type Success = C;
+
+    type Failure = B;
+
+    fn to_result(self) -> Result<Self::Success, Self::Failure> {
+        match self {
+            ControlFlow::Continue(suc) => Ok(suc),
+            ControlFlow::Break(err) => Err(err),
+        }
+    }
+}
+
 impl Fallible for () {
     type Success = ();
 
@@ -83,6 +186,33 @@ impl Fallible for () {
     }
 }
 
+/// build the early return value from the failure, used by [`rtry!`](crate::rtry)
+/// and [`rbail!`](crate::rbail)
+#[doc(hidden)]
+pub trait FromFailure<E> {
+    fn from_failure(err: E) -> Self;
+}
+
+impl<T, E, Ef> FromFailure<Ef> for Result<T, E>
+where
+    E: From<Ef>,
+{
+    #[inline]
+    fn from_failure(err: Ef) -> Self {
+        Err(err.into())
+    }
+}
+
+impl<T, E, Ef> FromFailure<Ef> for RespResult<T, E>
+where
+    E: From<Ef>,
+{
+    #[inline]
+    fn from_failure(err: Ef) -> Self {
+        RespResult::Err(err.into())
+    }
+}
+
 #[inline]
 /// receive a [Future](core::future::Future) applying it immediately, then convent the result into [RespResult](crate::RespResult)
 pub async fn resp_try<Fut, T, E>(future: Fut) -> RespResult<T, E>
@@ -93,3 +223,69 @@ where
 {
     future.await.into_rresult()
 }
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, ops::ControlFlow};
+
+    use http::StatusCode;
+
+    use crate::{
+        IntoRespResult, IntoRespResultWithErr, NotFoundError, OrNotFound, RespError, RespResult,
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum MockErr {
+        NotFound,
+        Other,
+    }
+
+    impl From<NotFoundError> for MockErr {
+        fn from(_: NotFoundError) -> Self {
+            MockErr::NotFound
+        }
+    }
+
+    impl RespError for MockErr {
+        fn log_message(&self) -> Cow<'_, str> {
+            "Mock Error".into()
+        }
+        #[cfg(feature = "extra-error")]
+        type ExtraMessage = String;
+        #[cfg(feature = "extra-error")]
+        fn extra_message(&self) -> Self::ExtraMessage {
+            "Mock".into()
+        }
+    }
+
+    #[test]
+    fn test_option() {
+        let resp: RespResult<i32, MockErr> = None.into_rresult();
+        assert!(matches!(resp, RespResult::Err(MockErr::NotFound)));
+
+        let resp: RespResult<i32, MockErr> = OrNotFound(Ok(None)).into_rresult();
+        assert!(matches!(resp, RespResult::Err(MockErr::NotFound)));
+
+        let resp: RespResult<i32, MockErr> = OrNotFound(Ok(Some(1))).into_rresult();
+        assert!(matches!(resp, RespResult::Success(1)));
+
+        let resp: RespResult<i32, NotFoundError> = None.into_rresult();
+        let RespResult::Err(err) = resp else {
+            panic!("expect not found")
+        };
+        assert_eq!(err.http_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_fallback_err() {
+        let resp: RespResult<i32, MockErr> = ControlFlow::Break(MockErr::NotFound).into_rresult();
+        assert!(matches!(resp, RespResult::Err(MockErr::NotFound)));
+
+        let resp: RespResult<i32, MockErr> =
+            ControlFlow::<(), _>::Break(()).into_with_err(MockErr::Other);
+        assert!(matches!(resp, RespResult::Err(MockErr::Other)));
+
+        let resp: RespResult<i32, MockErr> = None.into_with_err(MockErr::Other);
+        assert!(matches!(resp, RespResult::Err(MockErr::Other)));
+    }
+}
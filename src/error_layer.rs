@@ -0,0 +1,284 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::response::{IntoResponse, Response};
+use http::{
+    header::{
+        CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, TRANSFER_ENCODING,
+    },
+    HeaderName, Request, StatusCode,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{Nil, RespError, RespResult, SimpleRespError};
+
+/// the max size of the original body read for the mapper
+const BODY_LIMIT: usize = 64 * 1024;
+
+/// the headers describing the original body, dropped from the wrapped response
+const BODY_HEADERS: [HeaderName; 6] = [
+    CONTENT_TYPE,
+    CONTENT_LENGTH,
+    CONTENT_ENCODING,
+    TRANSFER_ENCODING,
+    CONTENT_RANGE,
+    ETAG,
+];
+
+/// the marker extension of the response produced by [`RespResult`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Enveloped;
+
+type DefaultMapper = fn(StatusCode, String) -> SimpleRespError;
+
+/// the default mapper, using the reason of status code as the message and
+/// the original body as the log message
+fn default_mapper(status: StatusCode, body: String) -> SimpleRespError {
+    let message = status.canonical_reason().unwrap_or("Unknown Error");
+    let body = if body.is_empty() {
+        message.to_owned()
+    } else {
+        body
+    };
+    SimpleRespError::new(status, message).with_log_message(body)
+}
+
+/// the [`Layer`] wrapping the error responses not produced by [`RespResult`]
+/// (e.g. the rejections of axum, the timeout of `tower_http`) into the response envelope
+///
+/// the error converted by the mapper from the original status code and body, the original
+/// status code and the headers not conflicting with the envelope are kept, except the headers
+/// describing the original body, e.g. `content-encoding` and `etag`
+///
+/// ## Default
+/// the error is [`SimpleRespError`] with the reason of status code as the message
+#[derive(Debug, Clone)]
+pub struct WrapErrorLayer<F = DefaultMapper> {
+    mapper: F,
+}
+
+impl Default for WrapErrorLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WrapErrorLayer {
+    /// create a new [`WrapErrorLayer`] with the default mapper
+    pub fn new() -> Self {
+        Self {
+            mapper: default_mapper,
+        }
+    }
+}
+
+impl<F> WrapErrorLayer<F> {
+    /// map the original status code and body into the error
+    pub fn with_mapper<M, E>(self, mapper: M) -> WrapErrorLayer<M>
+    where
+        M: Fn(StatusCode, String) -> E,
+        E: RespError,
+    {
+        WrapErrorLayer { mapper }
+    }
+}
+
+impl<S, F: Clone> Layer<S> for WrapErrorLayer<F> {
+    type Service = WrapErrorService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        WrapErrorService {
+            inner,
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+/// the [`Service`] generated by [`WrapErrorLayer`]
+#[derive(Debug, Clone)]
+pub struct WrapErrorService<S, F = DefaultMapper> {
+    inner: S,
+    mapper: F,
+}
+
+impl<S, F, E, B> Service<Request<B>> for WrapErrorService<S, F>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    F: Fn(StatusCode, String) -> E + Clone + Send + 'static,
+    E: RespError,
+{
+    type Response = Response;
+
+    type Error = S::Error;
+
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let future = self.inner.call(req);
+        let mapper = self.mapper.clone();
+        Box::pin(async move {
+            let resp = future.await?;
+            let status = resp.status();
+            if !(status.is_client_error() || status.is_server_error())
+                || resp.extensions().get::<Enveloped>().is_some()
+            {
+                return Ok(resp);
+            }
+            Ok(wrap(resp, mapper).await)
+        })
+    }
+}
+
+async fn wrap<F, E>(resp: Response, mapper: F) -> Response
+where
+    F: Fn(StatusCode, String) -> E,
+    E: RespError,
+{
+    let (parts, body) = resp.into_parts();
+    let body = axum::body::to_bytes(body, BODY_LIMIT)
+        .await
+        .map(|body| String::from_utf8_lossy(&body).into_owned())
+        .unwrap_or_default();
+
+    let mut wrapped = RespResult::<Nil, E>::Err(mapper(parts.status, body)).into_response();
+    *wrapped.status_mut() = parts.status;
+    // 保留与响应信封不冲突的原始响应头
+    for key in parts.headers.keys() {
+        if BODY_HEADERS.contains(key) || wrapped.headers().contains_key(key) {
+            continue;
+        }
+        for value in parts.headers.get_all(key) {
+            wrapped.headers_mut().append(key, value.clone());
+        }
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, convert::Infallible};
+
+    use axum::{
+        body::Body,
+        response::{IntoResponse, Response},
+    };
+    use http::{header, Request, StatusCode};
+    use tower::{service_fn, ServiceExt};
+    use tower_layer::Layer;
+
+    use super::WrapErrorLayer;
+    use crate::{Nil, RespError, RespResult, SimpleRespError};
+
+    async fn body_json(resp: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_wrap_plain() {
+        let svc = WrapErrorLayer::new().layer(service_fn(|_: Request<Body>| async {
+            let resp = (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                [(header::RETRY_AFTER, "10")],
+                "length limit exceeded",
+            );
+            Ok::<_, Infallible>(resp.into_response())
+        }));
+
+        let resp = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "10");
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+        let body = body_json(resp).await;
+        assert_eq!(body["is-ok"], false);
+        assert_eq!(body["error-message"], "Payload Too Large");
+    }
+
+    #[tokio::test]
+    async fn test_drop_body_headers() {
+        let svc = WrapErrorLayer::new().layer(service_fn(|_: Request<Body>| async {
+            let resp = (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [
+                    (header::CONTENT_ENCODING, "gzip"),
+                    (header::ETAG, "\"abc\""),
+                    (header::RETRY_AFTER, "10"),
+                ],
+                vec![0x1f, 0x8b, 0x08, 0x00],
+            );
+            Ok::<_, Infallible>(resp.into_response())
+        }));
+
+        let resp = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!resp.headers().contains_key(header::CONTENT_ENCODING));
+        assert!(!resp.headers().contains_key(header::ETAG));
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "10");
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+        // the wrapped body is plain json
+        let body = body_json(resp).await;
+        assert_eq!(body["error-message"], "Service Unavailable");
+    }
+
+    #[tokio::test]
+    async fn test_keep_enveloped() {
+        let layer = WrapErrorLayer::new()
+            .with_mapper(|status, _| SimpleRespError::new(status, "should not be wrapped"));
+        let svc = layer.layer(service_fn(|_: Request<Body>| async {
+            let resp = RespResult::<Nil, _>::Err(SimpleRespError::new(
+                StatusCode::CONFLICT,
+                "already exists",
+            ));
+            Ok::<_, Infallible>(resp.into_response())
+        }));
+
+        let resp = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert_eq!(body_json(resp).await["error-message"], "already exists");
+    }
+
+    #[tokio::test]
+    async fn test_mapper() {
+        struct Timeout(String);
+
+        impl RespError for Timeout {
+            fn log_message(&self) -> Cow<'_, str> {
+                Cow::Borrowed(&self.0)
+            }
+
+            fn resp_message(&self) -> Cow<'_, str> {
+                "please retry later".into()
+            }
+
+            #[cfg(feature = "extra-error")]
+            type ExtraMessage = String;
+
+            #[cfg(feature = "extra-error")]
+            fn extra_message(&self) -> Self::ExtraMessage {
+                String::new()
+            }
+        }
+
+        let layer = WrapErrorLayer::new().with_mapper(|_, body| Timeout(body));
+        let svc = layer.layer(service_fn(|_: Request<Body>| async {
+            Ok::<_, Infallible>(StatusCode::REQUEST_TIMEOUT.into_response())
+        }));
+
+        let resp = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        // keep the original status code
+        assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(body_json(resp).await["error-message"], "please retry later");
+    }
+}
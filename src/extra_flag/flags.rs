@@ -91,12 +91,11 @@ impl ExtraFlag {
 ///
 /// ```rust
 ///
-/// use crate::{ExtraFlag,ExtraFlags};
-/// use http::StatusCode;
+/// use axum_resp_result::{ExtraFlag, ExtraFlags, StatusCode};
 ///
 /// let mut flags: ExtraFlags = ExtraFlag::empty_body() + ExtraFlag::status(StatusCode::BAD_REQUEST);
 ///
-///flags += ExtraFlag::append_header("bar","foo");
+/// flags += ExtraFlag::append_header("bar","foo");
 ///
 /// ```
 pub struct ExtraFlags {
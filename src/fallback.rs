@@ -0,0 +1,65 @@
+use http::{Method, Uri};
+
+use crate::{Nil, RespError, RespResult, RouteError};
+
+/// the fallback handler of [`Router`](axum::Router), respond the
+/// [`RouteError::NotFound`] converted into `E`
+///
+/// ```rust
+/// use axum::Router;
+/// use axum_resp_result::{fallback, SimpleRespError};
+///
+/// let router: Router = Router::new().fallback(fallback::<SimpleRespError>);
+/// ```
+pub async fn fallback<E>(method: Method, uri: Uri) -> RespResult<Nil, E>
+where
+    E: From<RouteError> + RespError,
+{
+    RespResult::Err(RouteError::NotFound { method, uri }.into())
+}
+
+/// the method not allowed fallback handler of [`Router`](axum::Router), respond the
+/// [`RouteError::MethodNotAllowed`] converted into `E`
+///
+/// the `Allow` header is kept by axum, unless `E` set it by [`RespError::append_headers`]
+///
+/// ```rust
+/// use axum::Router;
+/// use axum_resp_result::{method_not_allowed, SimpleRespError};
+///
+/// let router: Router =
+///     Router::new().method_not_allowed_fallback(method_not_allowed::<SimpleRespError>);
+/// ```
+pub async fn method_not_allowed<E>(method: Method, uri: Uri) -> RespResult<Nil, E>
+where
+    E: From<RouteError> + RespError,
+{
+    RespResult::Err(RouteError::MethodNotAllowed { method, uri }.into())
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use http::{header, StatusCode};
+    use tower::ServiceExt;
+
+    use super::{fallback, method_not_allowed};
+    use crate::SimpleRespError;
+
+    #[tokio::test]
+    async fn test_fallback() {
+        let router = Router::new()
+            .route("/users", get(|| async {}))
+            .fallback(fallback::<SimpleRespError>)
+            .method_not_allowed_fallback(method_not_allowed::<SimpleRespError>);
+
+        let req = Request::get("/posts").body(Body::empty()).unwrap();
+        let resp = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = Request::post("/users").body(Body::empty()).unwrap();
+        let resp = router.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(resp.headers()[header::ALLOW], "GET,HEAD");
+    }
+}
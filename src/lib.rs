@@ -2,50 +2,95 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../Readme.md")]
 
+// 测试中使用派生宏，生成的代码以 `::axum_resp_result` 引用本 crate
+#[cfg(test)]
+extern crate self as axum_resp_result;
+
+#[cfg(feature = "catch-panic")]
+mod catch_panic;
 mod config;
 mod convert;
+mod error_layer;
 mod expect_ext;
 mod extra_flag;
+mod fallback;
 mod owner_leak;
 mod resp_body;
+mod resp_context;
 mod resp_error;
 mod resp_result;
 
 pub use self::resp_result::to_response::axum::axum_respond_part;
 use once_cell::sync::OnceCell;
 
+#[cfg(feature = "catch-panic")]
+pub use catch_panic::{catch_panic_layer, RespPanicHandler};
 use config::InnerConfig;
-pub use config::{ConfigTrait, DefaultConfig, RespConfig, SerdeConfig, SignType, StatusSign};
+pub use config::{
+    CodeSource, ConfigProblem, ConfigTrait, DefaultConfig, MetaField, MetaType, RespConfig,
+    SerdeConfig, SetRespResultConfigureError, SignType, StatusSign,
+};
+#[cfg(feature = "garde")]
+pub use convert::from_request::valid::{GardeBackend, GardeForm, GardeJson, GardePath, GardeQuery};
+#[cfg(any(feature = "validator", feature = "garde"))]
+pub use convert::from_request::valid::{ValidBackend, ValidForm, ValidJson, ValidPath, ValidQuery};
+#[cfg(feature = "validator")]
+pub use convert::from_request::valid::{
+    ValidatorBackend, ValidatorForm, ValidatorJson, ValidatorPath, ValidatorQuery,
+};
 pub use convert::{
-    from_request::{FromRequestFamily, MapReject, ToInner},
-    resp_try, IntoRespResult, IntoRespResultWithErr,
+    from_request::{FromRequestFamily, MapReject, MergeRejection, ToInner, TryMapReject},
+    resp_try, IntoRespResult, IntoRespResultWithErr, OrNotFound,
 };
+pub use error_layer::{WrapErrorLayer, WrapErrorService};
 pub use extra_flag::{
     flag_wrap::FlagWrap,
     flags::{ExtraFlag, ExtraFlags, HeaderType},
 };
-pub use resp_error::RespError;
+pub use fallback::{fallback, method_not_allowed};
+pub use resp_context::RespContext;
+#[cfg(feature = "context")]
+pub use resp_context::{RespContextLayer, RespContextService};
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+pub use resp_error::register_resp_error;
+#[cfg(feature = "anyhow")]
+pub use resp_error::AnyhowRespError;
+#[cfg(feature = "eyre")]
+pub use resp_error::EyreRespError;
+#[cfg(feature = "catch-panic")]
+pub use resp_error::PanicError;
+#[cfg(feature = "rejection")]
+pub use resp_error::RejectionError;
+pub use resp_error::{
+    DynRespError, ErrorDetail, IntoDynRespError, NotFoundError, RespError, RouteError,
+    SimpleRespError,
+};
 pub use resp_result::{Nil, RespResult};
 
 pub type FlagRespResult<T, E> = RespResult<FlagWrap<T>, E>;
 
 static RESP_RESULT_CONFIG: OnceCell<InnerConfig> = OnceCell::new();
 
+/// try to set the [`RespResult`] config
+///
+/// the config will be validated before set, all the problems will be reported
+/// by [`SetRespResultConfigureError::Invalid`]
 pub fn try_set_config<C: ConfigTrait>(cfg: &C) -> Result<(), SetRespResultConfigureError> {
-    let inner = InnerConfig::from_cfg(cfg);
+    let inner = InnerConfig::from_cfg(cfg).map_err(SetRespResultConfigureError::Invalid)?;
 
     #[cfg(feature = "trace")]
     tracing::event!(tracing::Level::DEBUG, set_config = "On Going");
     RESP_RESULT_CONFIG
         .set(inner)
-        .map_err(|_| SetRespResultConfigureError)
+        .map_err(|_| SetRespResultConfigureError::AlreadySet)
 }
 
 /// set the [`RespResult`] config, will change the action on generate response body
 ///
 /// ## Panic
 ///
-/// the config can only been set once, multiple times set will cause panic
+/// the config can only been set once, multiple times set will cause panic.
+/// invalid config will also cause panic
 pub fn set_config<C: ConfigTrait>(cfg: &C) {
     match try_set_config(cfg) {
         Ok(_) => {
@@ -71,12 +116,32 @@ pub(crate) fn get_config() -> &'static InnerConfig {
         Default::default()
     })
 }
-#[derive(Debug, thiserror::Error)]
-#[error("RespResult Configure has set")]
-pub struct SetRespResultConfigureError;
+
+/// read the config without locking it in, using the default one before the config set
+///
+/// for the values may be read before [`set_config`], e.g. the message of [`NotFoundError`]
+pub(crate) fn peek_config() -> &'static InnerConfig {
+    static DEFAULT_CONFIG: OnceCell<InnerConfig> = OnceCell::new();
+    RESP_RESULT_CONFIG
+        .get()
+        .unwrap_or_else(|| DEFAULT_CONFIG.get_or_init(Default::default))
+}
 
 pub use axum_resp_result_macro::resp_result as rresult;
 pub use axum_resp_result_macro::resp_result;
 pub use axum_resp_result_macro::RespError;
+pub use axum_resp_result_macro::ToInner;
+pub use axum_resp_result_macro::{RespFromRequest, RespFromRequestParts};
 pub use convert::Fallible;
 pub use http::StatusCode;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::convert::from_request::{merge_rejection, take_rejection};
+    pub use crate::convert::FromFailure;
+    pub use axum;
+    #[cfg(feature = "rejection")]
+    pub use axum::extract::rejection;
+    #[cfg(feature = "trace")]
+    pub use tracing;
+}
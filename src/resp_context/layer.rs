@@ -0,0 +1,110 @@
+use std::task::{Context, Poll};
+
+use http::{header::HeaderName, Request};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use super::{RespContext, RESP_CONTEXT};
+
+static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// the [`Layer`] that set up [`RespContext`] for each request
+///
+/// ## Default
+/// the request id is read from the header `x-request-id`
+#[derive(Debug, Clone)]
+pub struct RespContextLayer {
+    request_id: HeaderName,
+}
+
+impl Default for RespContextLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RespContextLayer {
+    /// create a new [`RespContextLayer`] reading request id from `x-request-id`
+    pub fn new() -> Self {
+        Self {
+            request_id: X_REQUEST_ID.clone(),
+        }
+    }
+
+    /// read the request id from the provide header
+    pub fn request_id_header(self, header: HeaderName) -> Self {
+        Self { request_id: header }
+    }
+}
+
+impl<S> Layer<S> for RespContextLayer {
+    type Service = RespContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RespContextService {
+            inner,
+            request_id: self.request_id.clone(),
+        }
+    }
+}
+
+/// the [`Service`] generated by [`RespContextLayer`]
+#[derive(Debug, Clone)]
+pub struct RespContextService<S> {
+    inner: S,
+    request_id: HeaderName,
+}
+
+impl<S, B> Service<Request<B>> for RespContextService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+
+    type Error = S::Error;
+
+    type Future = TaskLocalFuture<RespContext, S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(&self.request_id)
+            .and_then(|v| v.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        RESP_CONTEXT.scope(RespContext::new(request_id), self.inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::Request;
+    use tower::{service_fn, ServiceExt};
+    use tower_layer::Layer;
+
+    use crate::RespContext;
+
+    use super::RespContextLayer;
+
+    #[tokio::test]
+    async fn test_context() {
+        let svc = RespContextLayer::new().layer(service_fn(|_: Request<()>| async {
+            Ok::<_, std::convert::Infallible>(RespContext::current())
+        }));
+
+        let req = Request::builder()
+            .header("x-request-id", "abc-123")
+            .body(())
+            .unwrap();
+        let ctx = svc.oneshot(req).await.unwrap().expect("context not set");
+
+        assert_eq!(ctx.request_id(), Some("abc-123"));
+        assert!(RespContext::current().is_none());
+    }
+}
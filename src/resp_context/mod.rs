@@ -0,0 +1,62 @@
+#[cfg(feature = "context")]
+mod layer;
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "context")]
+pub use self::layer::{RespContextLayer, RespContextService};
+
+#[cfg(feature = "context")]
+tokio::task_local! {
+    static RESP_CONTEXT: RespContext;
+}
+
+/// the per-request context used for filling the [`MetaField`](crate::MetaField)s
+/// of the response envelope
+///
+/// the context is set by [`RespContextLayer`] for each request,
+/// which require feature `context`
+#[derive(Debug, Clone)]
+pub struct RespContext {
+    request_id: Option<String>,
+    timestamp: SystemTime,
+    start: Instant,
+}
+
+impl RespContext {
+    /// create a new [`RespContext`] with provide `request_id`,
+    /// the timestamp and the start instant are set to now
+    pub fn new(request_id: Option<String>) -> Self {
+        Self {
+            request_id,
+            timestamp: SystemTime::now(),
+            start: Instant::now(),
+        }
+    }
+
+    /// the request id of current request
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// the time of receiving the request, milliseconds since the unix epoch
+    pub fn timestamp_ms(&self) -> u64 {
+        self.timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    /// the milliseconds elapsed since receiving the request
+    pub fn elapsed_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+
+    /// get the [`RespContext`] of current request
+    /// - `None` if not inside the [`RespContextLayer`]
+    #[cfg(feature = "context")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "context")))]
+    pub fn current() -> Option<Self> {
+        RESP_CONTEXT.try_with(Clone::clone).ok()
+    }
+}
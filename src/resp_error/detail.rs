@@ -0,0 +1,29 @@
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+/// a field-level detail of the error, e.g. one invalid field of the request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorDetail {
+    /// the path of the field
+    pub field: Cow<'static, str>,
+    /// the machine readable code of the error
+    pub code: Cow<'static, str>,
+    /// the human readable message of the error
+    pub message: Cow<'static, str>,
+}
+
+impl ErrorDetail {
+    /// create a new [`ErrorDetail`]
+    pub fn new(
+        field: impl Into<Cow<'static, str>>,
+        code: impl Into<Cow<'static, str>>,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::{any::Any, borrow::Cow, convert::Infallible, fmt::Debug};
+
+use http::{HeaderMap, StatusCode};
+
+use super::{ErrorDetail, NotFoundError, RespError, SimpleRespError};
+#[cfg(feature = "extra-error")]
+use crate::expect_ext::ExpectExt;
+use crate::MergeRejection;
+
+/// the object safe part of [`RespError`]
+pub(super) trait ErasedRespError: Send + Sync + 'static {
+    fn log_message(&self) -> Cow<'_, str>;
+    fn resp_message(&self) -> Cow<'_, str>;
+    fn http_code(&self) -> StatusCode;
+    fn business_code(&self) -> i64;
+    fn append_headers(&self, headers: &mut HeaderMap);
+    fn details(&self) -> Option<Vec<ErrorDetail>>;
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> serde_json::Value;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<E> ErasedRespError for E
+where
+    E: RespError + Send + Sync + 'static,
+{
+    fn log_message(&self) -> Cow<'_, str> {
+        RespError::log_message(self)
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        RespError::resp_message(self)
+    }
+
+    fn http_code(&self) -> StatusCode {
+        RespError::http_code(self)
+    }
+
+    fn business_code(&self) -> i64 {
+        RespError::business_code(self)
+    }
+
+    fn append_headers(&self, headers: &mut HeaderMap) {
+        RespError::append_headers(self, headers)
+    }
+
+    fn details(&self) -> Option<Vec<ErrorDetail>> {
+        RespError::details(self)
+    }
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> serde_json::Value {
+        serde_json::to_value(RespError::extra_message(self)).with_expect("Bad Extra Message")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// the type erased [`RespError`], can hold any error whose concrete type is unknown
+///
+/// the status code, messages, extra message (serialized into json), headers and details of
+/// the original error are preserved, but [`RespError::resp_message_default`] and
+/// [`RespError::extra_message_default`] always using the default
+///
+/// > note: a blanket `From<E: RespError>` is impossible since [`DynRespError`] itself
+/// > implements [`RespError`], using [`DynRespError::new`] or [`IntoDynRespError::into_dyn`] instead.
+/// > the `RespError` derive macro generates the `From` impl with `#[resp_result(dyn_error)]`
+pub struct DynRespError {
+    inner: Box<dyn ErasedRespError>,
+}
+
+impl DynRespError {
+    /// erase the type of `err`
+    pub fn new<E>(err: E) -> Self
+    where
+        E: RespError + Send + Sync + 'static,
+    {
+        Self {
+            inner: Box::new(err),
+        }
+    }
+
+    /// whether the original error is `E`
+    pub fn is<E: 'static>(&self) -> bool {
+        self.inner.as_any().is::<E>()
+    }
+
+    /// get the reference of original error if it is `E`
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        self.inner.as_any().downcast_ref()
+    }
+}
+
+/// convert the error into [`DynRespError`]
+pub trait IntoDynRespError {
+    fn into_dyn(self) -> DynRespError;
+}
+
+impl<E> IntoDynRespError for E
+where
+    E: RespError + Send + Sync + 'static,
+{
+    #[inline]
+    fn into_dyn(self) -> DynRespError {
+        DynRespError::new(self)
+    }
+}
+
+impl Debug for DynRespError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DynRespError")
+            .field(&self.inner.log_message())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for DynRespError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner.log_message())
+    }
+}
+
+impl std::error::Error for DynRespError {}
+
+impl RespError for DynRespError {
+    fn log_message(&self) -> Cow<'_, str> {
+        self.inner.log_message()
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        self.inner.resp_message()
+    }
+
+    fn http_code(&self) -> StatusCode {
+        self.inner.http_code()
+    }
+
+    fn business_code(&self) -> i64 {
+        self.inner.business_code()
+    }
+
+    fn append_headers(&self, headers: &mut HeaderMap) {
+        self.inner.append_headers(headers)
+    }
+
+    fn details(&self) -> Option<Vec<ErrorDetail>> {
+        self.inner.details()
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = serde_json::Value;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        self.inner.extra_message()
+    }
+}
+
+/// merged into a [`SimpleRespError`], following the rule of [`SimpleRespError`]
+impl MergeRejection for DynRespError {
+    fn merge(self, other: Self) -> Self {
+        Self::new(SimpleRespError::merged(&self, &other))
+    }
+}
+
+impl From<SimpleRespError> for DynRespError {
+    fn from(err: SimpleRespError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<Infallible> for DynRespError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+impl From<NotFoundError> for DynRespError {
+    fn from(err: NotFoundError) -> Self {
+        Self::new(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::{HeaderMap, StatusCode};
+
+    use super::{DynRespError, IntoDynRespError};
+    use crate::{MergeRejection, NotFoundError, RespError, SimpleRespError};
+
+    #[test]
+    fn test_erased() {
+        let errs: Vec<DynRespError> = vec![
+            NotFoundError.into(),
+            SimpleRespError::new(StatusCode::CONFLICT, "conflict")
+                .with_log_message("user 1 exist")
+                .with_header("x-user", "1")
+                .into_dyn(),
+        ];
+
+        assert!(errs[0].is::<NotFoundError>());
+        assert_eq!(errs[0].http_code(), StatusCode::NOT_FOUND);
+
+        let err = &errs[1];
+        assert_eq!(err.http_code(), StatusCode::CONFLICT);
+        assert_eq!(err.resp_message(), "conflict");
+        assert_eq!(err.log_message(), "user 1 exist");
+        assert_eq!(err.business_code(), 409);
+        assert_eq!(
+            err.downcast_ref::<SimpleRespError>().map(|e| e.message()),
+            Some("conflict")
+        );
+        let mut headers = HeaderMap::new();
+        err.append_headers(&mut headers);
+        assert_eq!(headers["x-user"], "1");
+        #[cfg(feature = "extra-error")]
+        assert_eq!(err.extra_message(), 409);
+    }
+
+    #[test]
+    fn test_merge() {
+        let err = DynRespError::from(NotFoundError)
+            .merge(SimpleRespError::from(StatusCode::BAD_GATEWAY).into_dyn());
+        assert!(err.is::<SimpleRespError>());
+        assert_eq!(err.http_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(err.details().unwrap().len(), 1);
+    }
+}
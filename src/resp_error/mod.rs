@@ -1,5 +1,33 @@
+mod detail;
+mod dynamic;
+mod not_found;
+#[cfg(feature = "catch-panic")]
+mod panic;
+#[cfg(feature = "rejection")]
+mod rejection;
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+mod report;
+mod route;
+mod simple;
+
 use std::borrow::Cow;
 
+pub use detail::ErrorDetail;
+pub use dynamic::{DynRespError, IntoDynRespError};
+pub use not_found::NotFoundError;
+#[cfg(feature = "catch-panic")]
+pub use panic::PanicError;
+#[cfg(feature = "rejection")]
+pub use rejection::RejectionError;
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+pub use report::register_resp_error;
+#[cfg(feature = "anyhow")]
+pub use report::AnyhowRespError;
+#[cfg(feature = "eyre")]
+pub use report::EyreRespError;
+pub use route::RouteError;
+pub use simple::SimpleRespError;
+
 /// the error when [`RespResult`](crate::RespResult) is `Err(_)`
 pub trait RespError {
     /// message for logger
@@ -22,6 +50,34 @@ pub trait RespError {
         http::StatusCode::INTERNAL_SERVER_ERROR
     }
 
+    /// the business code of this error, used by the status sign with
+    /// [`CodeSource::BusinessCode`](crate::CodeSource::BusinessCode)
+    ///
+    /// ## Default
+    /// the default business code is the number of [`RespError::http_code`]
+    #[inline]
+    fn business_code(&self) -> i64 {
+        self.http_code().as_u16().into()
+    }
+
+    /// append the extra headers into the response when [`RespResult`](crate::RespResult) is `Err(_)`
+    ///
+    /// ## Default
+    /// default append nothing
+    #[inline]
+    #[allow(unused_variables)]
+    fn append_headers(&self, headers: &mut http::HeaderMap) {}
+
+    /// the field-level details of this error, serialized into the field
+    /// [`SerdeConfig::details_name`](crate::SerdeConfig::details_name)
+    ///
+    /// ## Default
+    /// default is [`None`], it will be serialized to `null`
+    #[inline]
+    fn details(&self) -> Option<Vec<ErrorDetail>> {
+        None
+    }
+
     #[cfg(feature = "extra-error")]
     /// the associate type of extra message
     type ExtraMessage: serde::Serialize + 'static + Sized + std::fmt::Display;
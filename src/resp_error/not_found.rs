@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+
+use super::RespError;
+use crate::peek_config;
+
+/// the error when the resource not found, e.g. the handler return `None`
+///
+/// the http code and message can be set by
+/// [`RespConfig`](crate::RespConfig), they are read when used,
+/// so creating or displaying the error before [`set_config`](crate::set_config) is fine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, thiserror::Error)]
+#[error("{}", peek_config().resp.not_found_message)]
+pub struct NotFoundError;
+
+impl RespError for NotFoundError {
+    fn log_message(&self) -> Cow<'_, str> {
+        peek_config().resp.not_found_message.into()
+    }
+
+    fn http_code(&self) -> http::StatusCode {
+        peek_config().resp.not_found_status
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = &'static str;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        peek_config().resp.not_found_message
+    }
+}
@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+use http::StatusCode;
+
+use super::{RespError, SimpleRespError};
+
+/// the message of panic never exposed to the client
+const PANIC_MESSAGE: &str = "Internal Server Error";
+
+/// the error when the handler panicked, caught by [`catch_panic_layer`](crate::catch_panic_layer)
+///
+/// the payload and location of panic only appear in the log message
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Handler Panicked at {}: {payload}", location.as_deref().unwrap_or("unknown"))]
+pub struct PanicError {
+    payload: String,
+    location: Option<String>,
+}
+
+impl PanicError {
+    /// create a new [`PanicError`] with the payload of panic
+    pub fn new(payload: impl Into<String>) -> Self {
+        Self {
+            payload: payload.into(),
+            location: None,
+        }
+    }
+
+    /// set the location of panic, e.g. `src/main.rs:10:5`
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// the payload of panic
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    /// the location of panic, only known with feature `trace`
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+}
+
+impl RespError for PanicError {
+    fn log_message(&self) -> Cow<'_, str> {
+        self.to_string().into()
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        PANIC_MESSAGE.into()
+    }
+
+    fn http_code(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = &'static str;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        PANIC_MESSAGE
+    }
+}
+
+impl From<PanicError> for SimpleRespError {
+    fn from(err: PanicError) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, PANIC_MESSAGE)
+            .with_log_message(err.to_string())
+    }
+}
@@ -0,0 +1,482 @@
+use std::{borrow::Cow, convert::Infallible, error::Error as StdError};
+
+use axum::extract::{
+    path::ErrorKind,
+    rejection::{
+        BytesRejection, ExtensionRejection, FailedToBufferBody, FormRejection, JsonRejection,
+        PathRejection, QueryRejection, RawFormRejection, StringRejection,
+    },
+};
+use http::StatusCode;
+
+use super::{ErrorDetail, RespError, SimpleRespError};
+use crate::MergeRejection;
+
+/// the structured error decomposed from the axum rejections,
+/// with the status code, a stable machine readable code and the offending field
+///
+/// implement `From<RejectionError>` for your error, then using
+/// [`impl_from_rejection!`](crate::impl_from_rejection) to implement the `From` of all
+/// the axum rejections, so the error can be used in [`MapReject`](crate::MapReject)
+///
+/// | rejection | code | status |
+/// |-----------|------|--------|
+/// | json data error | `json_data` | 422 |
+/// | json syntax error | `json_syntax` | 400 |
+/// | missing json content type | `json_content_type` | 415 |
+/// | query deserialize error | `query` | 400 |
+/// | form deserialize error (`GET`/`HEAD`) | `form` | 400 |
+/// | form body deserialize error | `form_body` | 422 |
+/// | invalid form content type | `form_content_type` | 415 |
+/// | wrong number of path params | `path_count` | 500 |
+/// | path param parse error | `path_parse` | 400 |
+/// | invalid utf-8 in path param | `path_utf8` | 400 |
+/// | path param deserialize error | `path_deserialize` | 400 |
+/// | unsupported path param type | `path_unsupported` | 500 |
+/// | missing path params | `path_missing` | 500 |
+/// | missing extension | `extension_missing` | 500 |
+/// | body too large | `body_too_large` | 413 |
+/// | failed to buffer body | `body_unknown` | 400 |
+/// | invalid utf-8 body | `body_utf8` | 400 |
+///
+/// the rejections with status `500` are the mistakes of server,
+/// their detail only appears in the log message
+///
+/// the rejections of several fields can be merged by [`MergeRejection`], following
+/// the rule of [`SimpleRespError`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{log_message}")]
+pub struct RejectionError {
+    status: StatusCode,
+    code: &'static str,
+    path: Option<String>,
+    message: String,
+    log_message: String,
+    /// the details of all the rejections, only set by merging
+    merged: Option<Vec<ErrorDetail>>,
+}
+
+impl RejectionError {
+    /// create a new [`RejectionError`]
+    pub fn new(
+        status: StatusCode,
+        code: &'static str,
+        path: Option<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        let message = message.into();
+        Self {
+            status,
+            code,
+            path,
+            log_message: message.clone(),
+            message,
+            merged: None,
+        }
+    }
+
+    /// set the log message, default is the response message
+    pub fn with_log_message(mut self, log_message: impl Into<String>) -> Self {
+        self.log_message = log_message.into();
+        self
+    }
+
+    /// the http status code
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// the machine readable code
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// the path of the offending field, e.g. `user.name` or the key of path params
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// the message for response
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// the deserialize error of the json body, query or form,
+    /// take the field path out of the source
+    fn deserialize<R: StdError>(status: StatusCode, code: &'static str, rejection: &R) -> Self {
+        let inner = rejection.source().and_then(StdError::source);
+        let (path, message) = if let Some(err) = inner
+            .and_then(|err| err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>())
+        {
+            (err.path().to_string(), err.inner().to_string())
+        } else if let Some(err) = inner.and_then(|err| {
+            err.downcast_ref::<serde_path_to_error::Error<serde::de::value::Error>>()
+        }) {
+            (err.path().to_string(), err.inner().to_string())
+        } else {
+            (".".to_owned(), rejection.to_string())
+        };
+        // 根路径与未知路径不作为字段
+        let path = Some(path).filter(|path| path != "." && path != "?");
+        Self::new(status, code, path, message).with_log_message(rejection.to_string())
+    }
+
+    /// the details of this rejection when merging, the rejection without path is recorded
+    /// as a detail with empty field, except the server error
+    fn field_details(&self) -> Vec<ErrorDetail> {
+        if let Some(merged) = &self.merged {
+            return merged.clone();
+        }
+        match &self.path {
+            Some(path) => vec![ErrorDetail::new(
+                path.clone(),
+                self.code,
+                self.message.clone(),
+            )],
+            // 服务端错误的信息不暴露给客户端
+            None if self.status.is_server_error() => Vec::new(),
+            None => vec![ErrorDetail::new("", self.code, self.message.clone())],
+        }
+    }
+
+    /// the mistake of server, hide the detail from response
+    fn internal(code: &'static str, rejection: &impl ToString) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            code,
+            None,
+            "Internal Server Error",
+        )
+        .with_log_message(rejection.to_string())
+    }
+}
+
+impl RespError for RejectionError {
+    fn log_message(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.log_message)
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.message)
+    }
+
+    fn http_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn details(&self) -> Option<Vec<ErrorDetail>> {
+        if let Some(merged) = &self.merged {
+            return Some(merged.clone());
+        }
+        let path = self.path.clone()?;
+        Some(vec![ErrorDetail::new(
+            path,
+            self.code,
+            self.message.clone(),
+        )])
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = &'static str;
+
+    /// the extra message is the machine readable code
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        self.code
+    }
+}
+
+/// the status code, code and message are taken from the first `5xx` rejection, otherwise
+/// the former one, the details of both are collected and the log messages are joined by `; `
+impl MergeRejection for RejectionError {
+    fn merge(self, other: Self) -> Self {
+        let details = self
+            .field_details()
+            .into_iter()
+            .chain(other.field_details())
+            .collect();
+        let log_message = format!("{}; {}", self.log_message, other.log_message);
+        let mut merged = if other.status.is_server_error() && !self.status.is_server_error() {
+            other
+        } else {
+            self
+        };
+        merged.log_message = log_message;
+        merged.merged = Some(details);
+        merged
+    }
+}
+
+impl From<RejectionError> for SimpleRespError {
+    fn from(err: RejectionError) -> Self {
+        let details = err.details().unwrap_or_default();
+        let simple = Self::new(err.status, err.message).with_log_message(err.log_message);
+        #[cfg(feature = "extra-error")]
+        let simple = simple.with_extra(err.code);
+        simple.with_details(details)
+    }
+}
+
+impl From<Infallible> for RejectionError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+impl From<FailedToBufferBody> for RejectionError {
+    fn from(err: FailedToBufferBody) -> Self {
+        let code = match err {
+            FailedToBufferBody::LengthLimitError(_) => "body_too_large",
+            _ => "body_unknown",
+        };
+        Self::new(err.status(), code, None, err.body_text())
+    }
+}
+
+impl From<BytesRejection> for RejectionError {
+    fn from(err: BytesRejection) -> Self {
+        match err {
+            BytesRejection::FailedToBufferBody(err) => err.into(),
+            err => Self::new(err.status(), "body_unknown", None, err.body_text()),
+        }
+    }
+}
+
+impl From<StringRejection> for RejectionError {
+    fn from(err: StringRejection) -> Self {
+        match err {
+            StringRejection::FailedToBufferBody(err) => err.into(),
+            err => Self::new(err.status(), "body_utf8", None, err.body_text()),
+        }
+    }
+}
+
+impl From<JsonRejection> for RejectionError {
+    fn from(err: JsonRejection) -> Self {
+        match err {
+            JsonRejection::JsonDataError(err) => Self::deserialize(err.status(), "json_data", &err),
+            JsonRejection::JsonSyntaxError(err) => {
+                Self::deserialize(err.status(), "json_syntax", &err)
+            }
+            JsonRejection::BytesRejection(err) => err.into(),
+            err => Self::new(err.status(), "json_content_type", None, err.body_text()),
+        }
+    }
+}
+
+impl From<QueryRejection> for RejectionError {
+    fn from(err: QueryRejection) -> Self {
+        Self::deserialize(err.status(), "query", &err)
+    }
+}
+
+impl From<FormRejection> for RejectionError {
+    fn from(err: FormRejection) -> Self {
+        match err {
+            FormRejection::FailedToDeserializeForm(err) => {
+                Self::deserialize(err.status(), "form", &err)
+            }
+            FormRejection::FailedToDeserializeFormBody(err) => {
+                Self::deserialize(err.status(), "form_body", &err)
+            }
+            FormRejection::BytesRejection(err) => err.into(),
+            err => Self::new(err.status(), "form_content_type", None, err.body_text()),
+        }
+    }
+}
+
+impl From<RawFormRejection> for RejectionError {
+    fn from(err: RawFormRejection) -> Self {
+        match err {
+            RawFormRejection::BytesRejection(err) => err.into(),
+            err => Self::new(err.status(), "form_content_type", None, err.body_text()),
+        }
+    }
+}
+
+impl From<PathRejection> for RejectionError {
+    fn from(err: PathRejection) -> Self {
+        let PathRejection::FailedToDeserializePathParams(err) = err else {
+            return Self::internal("path_missing", &err);
+        };
+        let (code, path, message) = match err.kind() {
+            ErrorKind::WrongNumberOfParameters { .. } => return Self::internal("path_count", &err),
+            ErrorKind::UnsupportedType { .. } => return Self::internal("path_unsupported", &err),
+            ErrorKind::ParseErrorAtKey {
+                key, expected_type, ..
+            } => (
+                "path_parse",
+                Some(key.clone()),
+                format!("Cannot parse to a `{expected_type}`"),
+            ),
+            ErrorKind::ParseErrorAtIndex {
+                index,
+                expected_type,
+                ..
+            } => (
+                "path_parse",
+                Some(index.to_string()),
+                format!("Cannot parse to a `{expected_type}`"),
+            ),
+            ErrorKind::ParseError { expected_type, .. } => (
+                "path_parse",
+                None,
+                format!("Cannot parse to a `{expected_type}`"),
+            ),
+            ErrorKind::InvalidUtf8InPathParam { key } => {
+                ("path_utf8", Some(key.clone()), "Invalid UTF-8".to_owned())
+            }
+            ErrorKind::DeserializeError { key, message, .. } => {
+                ("path_deserialize", Some(key.clone()), message.clone())
+            }
+            kind => ("path_deserialize", None, kind.to_string()),
+        };
+        Self::new(err.status(), code, path, message).with_log_message(err.body_text())
+    }
+}
+
+impl From<ExtensionRejection> for RejectionError {
+    fn from(err: ExtensionRejection) -> Self {
+        Self::internal("extension_missing", &err)
+    }
+}
+
+/// implement `From` of all the axum rejections and [`Infallible`](std::convert::Infallible)
+/// for the error implemented `From<RejectionError>`
+///
+/// ```rust
+/// use axum_resp_result::{impl_from_rejection, RejectionError};
+///
+/// #[derive(Debug)]
+/// struct MyError(RejectionError);
+///
+/// impl From<RejectionError> for MyError {
+///     fn from(err: RejectionError) -> Self {
+///         Self(err)
+///     }
+/// }
+///
+/// impl_from_rejection!(MyError);
+/// ```
+#[macro_export]
+macro_rules! impl_from_rejection {
+    ($($err:ty),+ $(,)?) => {
+        $(
+            impl ::core::convert::From<::core::convert::Infallible> for $err {
+                fn from(err: ::core::convert::Infallible) -> Self {
+                    match err {}
+                }
+            }
+            $crate::impl_from_rejection!(@rejections $err);
+        )+
+    };
+    (@rejections $err:ty) => {
+        $crate::impl_from_rejection!(
+            @impl $err;
+            BytesRejection, StringRejection, JsonRejection, QueryRejection,
+            FormRejection, RawFormRejection, PathRejection, ExtensionRejection
+        );
+    };
+    (@impl $err:ty; $($rejection:ident),+) => {
+        $(
+            impl ::core::convert::From<$crate::__private::rejection::$rejection> for $err {
+                fn from(err: $crate::__private::rejection::$rejection) -> Self {
+                    <$err as ::core::convert::From<$crate::RejectionError>>::from(
+                        $crate::RejectionError::from(err),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+// `SimpleRespError` 自身实现了 `From<Infallible>`
+impl_from_rejection!(@rejections SimpleRespError);
+
+#[cfg(test)]
+mod test {
+    use axum::{
+        body::Body,
+        extract::{FromRequest, Path, Query, Request},
+        routing::get,
+        Json, Router,
+    };
+    use http::{StatusCode, Uri};
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::RejectionError;
+    use crate::RespError;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        id: u32,
+        profile: Profile,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Profile {
+        name: String,
+    }
+
+    async fn json(body: &'static str, content_type: &str) -> RejectionError {
+        let req = Request::builder()
+            .header("content-type", content_type)
+            .body(body.into())
+            .unwrap();
+        Json::<User>::from_request(req, &())
+            .await
+            .unwrap_err()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_json() {
+        let err = json(r#"{"id": 1, "profile": {"name": 1}}"#, "application/json").await;
+        assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.code(), "json_data");
+        assert_eq!(err.path(), Some("profile.name"));
+        assert_eq!(err.details().unwrap()[0].field, "profile.name");
+
+        let err = json("{", "application/json").await;
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.code(), "json_syntax");
+        assert_eq!(err.path(), None);
+
+        let err = json("{}", "text/plain").await;
+        assert_eq!(err.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(err.code(), "json_content_type");
+    }
+
+    #[test]
+    fn test_query() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Page {
+            size: u32,
+        }
+
+        let uri = Uri::from_static("/users?size=abc");
+        let err = RejectionError::from(Query::<Page>::try_from_uri(&uri).unwrap_err());
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.code(), "query");
+        assert_eq!(err.path(), Some("size"));
+    }
+
+    #[tokio::test]
+    async fn test_path() {
+        async fn handler(path: Result<Path<(u32,)>, axum::extract::rejection::PathRejection>) {
+            let err = RejectionError::from(path.unwrap_err());
+            assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(err.code(), "path_parse");
+            assert_eq!(err.path(), Some("0"));
+            assert_eq!(err.resp_message(), "Cannot parse to a `u32`");
+        }
+
+        let router = Router::new().route("/users/{id}", get(handler));
+        let req = Request::get("/users/abc").body(Body::empty()).unwrap();
+        router.oneshot(req).await.unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    error::Error as StdError,
+    sync::{PoisonError, RwLock},
+};
+
+use super::{dynamic::ErasedRespError, ErrorDetail, RespError};
+
+type Probe = for<'e> fn(&'e (dyn StdError + 'static)) -> Option<&'e dyn ErasedRespError>;
+
+/// the downcasters of all registered error types, keyed by the type id
+static PROBES: RwLock<Vec<(TypeId, Probe)>> = RwLock::new(Vec::new());
+
+fn probe<'e, E>(err: &'e (dyn StdError + 'static)) -> Option<&'e dyn ErasedRespError>
+where
+    E: RespError + StdError + Send + Sync + 'static,
+{
+    err.downcast_ref::<E>()
+        .map(|err| err as &dyn ErasedRespError)
+}
+
+/// register the domain error type `E`, then the error reports (e.g. `anyhow::Error`)
+/// containing `E` will using the status code and message of `E`
+///
+/// the error chain of the report will be searched from the outermost when the wrapper created,
+/// the first found registered error will be used. Register the errors on startup,
+/// the wrapper created before registering will not see the error
+pub fn register_resp_error<E>()
+where
+    E: RespError + StdError + Send + Sync + 'static,
+{
+    let mut probes = PROBES.write().unwrap_or_else(PoisonError::into_inner);
+    let id = TypeId::of::<E>();
+    if probes.iter().all(|(registered, _)| *registered != id) {
+        probes.push((id, probe::<E>));
+    }
+}
+
+/// the position in the error chain and the downcaster of the first registered error
+#[derive(Clone, Copy)]
+struct Located {
+    depth: usize,
+    probe: Probe,
+}
+
+impl Located {
+    fn find<'e>(chain: impl Iterator<Item = &'e (dyn StdError + 'static)>) -> Option<Self> {
+        let probes = PROBES.read().unwrap_or_else(PoisonError::into_inner);
+        if probes.is_empty() {
+            return None;
+        }
+        chain.enumerate().find_map(|(depth, err)| {
+            probes
+                .iter()
+                .find(|(_, probe)| probe(err).is_some())
+                .map(|&(_, probe)| Self { depth, probe })
+        })
+    }
+
+    fn get<'e>(
+        self,
+        mut chain: impl Iterator<Item = &'e (dyn StdError + 'static)>,
+    ) -> Option<&'e dyn ErasedRespError> {
+        chain.nth(self.depth).and_then(self.probe)
+    }
+}
+
+fn full_chain<'e>(chain: impl Iterator<Item = &'e (dyn StdError + 'static)>) -> String {
+    chain
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+static SANITIZED_MESSAGE: &str = "Internal Server Error";
+
+macro_rules! report_resp_error {
+    ($(#[$meta:meta])* $name:ident, $report:ty) => {
+        $(#[$meta])*
+        ///
+        /// - the registered error (see [`register_resp_error`]) found in the error chain
+        ///   decides the status code, response message, headers and details
+        /// - otherwise it is a `500 Internal Server Error` without leaking the detail
+        ///
+        /// the log message is always the full error chain
+        pub struct $name {
+            report: $report,
+            /// the registered error resolved on creating
+            domain: Option<Located>,
+        }
+
+        impl $name {
+            /// get the inner report
+            pub fn into_inner(self) -> $report {
+                self.report
+            }
+
+            fn domain_error(&self) -> Option<&dyn ErasedRespError> {
+                self.domain.and_then(|domain| domain.get(self.report.chain()))
+            }
+        }
+
+        impl<E> From<E> for $name
+        where
+            E: Into<$report>,
+        {
+            fn from(err: E) -> Self {
+                let report = err.into();
+                let domain = Located::find(report.chain());
+                Self { report, domain }
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Debug::fmt(&self.report, f)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.report, f)
+            }
+        }
+
+        impl RespError for $name {
+            fn log_message(&self) -> Cow<'_, str> {
+                full_chain(self.report.chain()).into()
+            }
+
+            fn resp_message(&self) -> Cow<'_, str> {
+                match self.domain_error() {
+                    Some(err) => err.resp_message(),
+                    None => SANITIZED_MESSAGE.into(),
+                }
+            }
+
+            fn http_code(&self) -> http::StatusCode {
+                self.domain_error()
+                    .map(|err| err.http_code())
+                    .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+
+            fn business_code(&self) -> i64 {
+                match self.domain_error() {
+                    Some(err) => err.business_code(),
+                    None => RespError::http_code(self).as_u16().into(),
+                }
+            }
+
+            fn append_headers(&self, headers: &mut http::HeaderMap) {
+                if let Some(err) = self.domain_error() {
+                    err.append_headers(headers)
+                }
+            }
+
+            fn details(&self) -> Option<Vec<ErrorDetail>> {
+                self.domain_error().and_then(|err| err.details())
+            }
+
+            #[cfg(feature = "extra-error")]
+            type ExtraMessage = serde_json::Value;
+
+            #[cfg(feature = "extra-error")]
+            fn extra_message(&self) -> Self::ExtraMessage {
+                self.domain_error()
+                    .map(|err| err.extra_message())
+                    .unwrap_or_default()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "anyhow")]
+report_resp_error!(
+    /// the [`RespError`] wrapper of [`anyhow::Error`]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+    AnyhowRespError,
+    anyhow::Error
+);
+
+#[cfg(feature = "eyre")]
+report_resp_error!(
+    /// the [`RespError`] wrapper of [`eyre::Report`]
+    #[cfg_attr(docsrs, doc(cfg(feature = "eyre")))]
+    EyreRespError,
+    eyre::Report
+);
+
+#[cfg(test)]
+mod test {
+    use http::StatusCode;
+
+    use super::register_resp_error;
+    use crate::{RespError, SimpleRespError};
+
+    #[test]
+    fn test_register_once() {
+        use std::any::TypeId;
+
+        use super::PROBES;
+
+        register_resp_error::<SimpleRespError>();
+        register_resp_error::<SimpleRespError>();
+        let probes = PROBES.read().unwrap();
+        let id = TypeId::of::<SimpleRespError>();
+        assert_eq!(
+            probes
+                .iter()
+                .filter(|(registered, _)| *registered == id)
+                .count(),
+            1
+        );
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_anyhow() {
+        use anyhow::Context;
+
+        use super::AnyhowRespError;
+
+        register_resp_error::<SimpleRespError>();
+
+        let err = AnyhowRespError::from(anyhow::anyhow!("db password wrong"));
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.resp_message(), "Internal Server Error");
+
+        let err: AnyhowRespError = Err::<(), _>(SimpleRespError::from(StatusCode::CONFLICT))
+            .context("create user")
+            .unwrap_err()
+            .into();
+        assert_eq!(err.http_code(), StatusCode::CONFLICT);
+        assert_eq!(err.resp_message(), "Conflict");
+        assert_eq!(err.log_message(), "create user: Conflict");
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn test_eyre() {
+        use eyre::WrapErr;
+
+        use super::EyreRespError;
+
+        register_resp_error::<SimpleRespError>();
+
+        let err: EyreRespError = Err::<(), _>(SimpleRespError::from(StatusCode::FORBIDDEN))
+            .wrap_err("delete user")
+            .unwrap_err()
+            .into();
+        assert_eq!(err.http_code(), StatusCode::FORBIDDEN);
+        assert_eq!(err.log_message(), "delete user: Forbidden");
+    }
+}
@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+
+use http::{Method, StatusCode, Uri};
+
+use super::{RespError, SimpleRespError};
+
+/// the error when the request matching no route, or the method not allowed on the route,
+/// produced by [`fallback`](crate::fallback) and [`method_not_allowed`](crate::method_not_allowed)
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RouteError {
+    /// no route matching the path
+    #[error("Route Not Found: {method} {uri}")]
+    NotFound { method: Method, uri: Uri },
+    /// the route exist, but not accept the method
+    #[error("Method Not Allowed: {method} {uri}")]
+    MethodNotAllowed { method: Method, uri: Uri },
+}
+
+impl RouteError {
+    /// the message for response, not including the request uri
+    pub fn message(&self) -> &'static str {
+        match self {
+            RouteError::NotFound { .. } => "Route Not Found",
+            RouteError::MethodNotAllowed { .. } => "Method Not Allowed",
+        }
+    }
+}
+
+impl RespError for RouteError {
+    fn log_message(&self) -> Cow<'_, str> {
+        self.to_string().into()
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        self.message().into()
+    }
+
+    fn http_code(&self) -> StatusCode {
+        match self {
+            RouteError::NotFound { .. } => StatusCode::NOT_FOUND,
+            RouteError::MethodNotAllowed { .. } => StatusCode::METHOD_NOT_ALLOWED,
+        }
+    }
+
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = &'static str;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        self.message()
+    }
+}
+
+impl From<RouteError> for SimpleRespError {
+    fn from(err: RouteError) -> Self {
+        Self::new(err.http_code(), err.message()).with_log_message(err.to_string())
+    }
+}
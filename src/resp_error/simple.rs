@@ -0,0 +1,467 @@
+use std::{borrow::Cow, convert::Infallible, fmt::Debug};
+
+use http::{header::HeaderName, HeaderMap, HeaderValue, StatusCode};
+
+use super::{ErrorDetail, NotFoundError, RespError};
+use crate::{expect_ext::ExpectExt, MergeRejection};
+
+/// a simple error with the http status code and message,
+/// usually created by [`resp_err!`](crate::resp_err) or the [`From`] impls
+///
+/// the log message, extra message and headers can be set in builder style
+/// ```rust
+/// use axum_resp_result::{RespError, SimpleRespError, StatusCode};
+///
+/// let err = SimpleRespError::new(StatusCode::TOO_MANY_REQUESTS, "Too Many Requests")
+///     .with_log_message("user 12 reach the rate limit")
+///     .with_header("retry-after", "10");
+///
+/// assert_eq!(err.resp_message(), "Too Many Requests");
+/// assert_eq!(err.log_message(), "user 12 reach the rate limit");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{}", self.log_message())]
+pub struct SimpleRespError {
+    status: StatusCode,
+    message: Cow<'static, str>,
+    /// boxed for keeping the error small
+    detail: Option<Box<Detail>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Detail {
+    log_message: Option<Cow<'static, str>>,
+    #[cfg(feature = "extra-error")]
+    extra: Option<serde_json::Value>,
+    headers: HeaderMap,
+    details: Option<Vec<ErrorDetail>>,
+}
+
+impl SimpleRespError {
+    /// create a new [`SimpleRespError`] with provide `status` and `message`
+    pub fn new(status: StatusCode, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
+    fn detail_mut(&mut self) -> &mut Detail {
+        self.detail.get_or_insert_with(Default::default)
+    }
+
+    /// set the message for logger, the message returned to the client keep unchanged
+    pub fn with_log_message(mut self, log_message: impl Into<Cow<'static, str>>) -> Self {
+        self.detail_mut().log_message = Some(log_message.into());
+        self
+    }
+
+    /// set the extra message
+    ///
+    /// ## Panic
+    /// the `extra` failed to serialize into json, see [`Self::try_with_extra`] for
+    /// the non-panicking version
+    #[cfg(feature = "extra-error")]
+    pub fn with_extra(self, extra: impl serde::Serialize) -> Self {
+        self.try_with_extra(extra).with_expect("Bad Extra Message")
+    }
+
+    /// set the extra message
+    /// - `Err(_)` if the `extra` failed to serialize into json
+    #[cfg(feature = "extra-error")]
+    pub fn try_with_extra(
+        mut self,
+        extra: impl serde::Serialize,
+    ) -> Result<Self, serde_json::Error> {
+        self.detail_mut().extra = Some(serde_json::to_value(extra)?);
+        Ok(self)
+    }
+
+    /// append a header to the response
+    ///
+    /// ## Panic
+    /// the `key` or `value` is invalid, see [`Self::try_with_header`] for
+    /// the non-panicking version
+    pub fn with_header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Debug,
+        V: TryInto<HeaderValue>,
+        V::Error: Debug,
+    {
+        self.detail_mut().headers.append(
+            key.try_into().with_expect("Bad Header Name"),
+            value.try_into().with_expect("Bad Header Value"),
+        );
+        self
+    }
+
+    /// append a header to the response
+    /// - `Err(_)` if the `key` or `value` is invalid
+    pub fn try_with_header<K, V>(mut self, key: K, value: V) -> Result<Self, http::Error>
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Into<http::Error>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<http::Error>,
+    {
+        let key = key.try_into().map_err(Into::into)?;
+        let value = value.try_into().map_err(Into::into)?;
+        self.detail_mut().headers.append(key, value);
+        Ok(self)
+    }
+
+    /// append a field-level detail
+    pub fn with_detail(mut self, detail: ErrorDetail) -> Self {
+        self.detail_mut()
+            .details
+            .get_or_insert_with(Vec::new)
+            .push(detail);
+        self
+    }
+
+    /// append the field-level details
+    pub fn with_details(mut self, details: impl IntoIterator<Item = ErrorDetail>) -> Self {
+        self.detail_mut()
+            .details
+            .get_or_insert_with(Vec::new)
+            .extend(details);
+        self
+    }
+
+    /// the http status code of this error
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// the message returned to the client
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl SimpleRespError {
+    /// merge two errors into one
+    ///
+    /// - the status code is the first `5xx` one, otherwise the status code of `former`
+    /// - the response message is the canonical reason of the status code
+    /// - the details of both are collected, the error without details is recorded as a detail
+    ///   with empty field, except the server error whose message is hidden from the client
+    /// - the log messages are joined by `; `, the headers of both are collected
+    /// - the extra message is taken from the error deciding the status code
+    pub(crate) fn merged(former: &impl RespError, later: &impl RespError) -> Self {
+        let later_wins =
+            later.http_code().is_server_error() && !former.http_code().is_server_error();
+        let status = if later_wins {
+            later.http_code()
+        } else {
+            former.http_code()
+        };
+
+        let mut merged = Self::from(status).with_log_message(format!(
+            "{}; {}",
+            former.log_message(),
+            later.log_message()
+        ));
+        let detail = merged.detail_mut();
+        former.append_headers(&mut detail.headers);
+        later.append_headers(&mut detail.headers);
+        detail.details = Some(
+            Self::field_details(former)
+                .into_iter()
+                .chain(Self::field_details(later))
+                .collect(),
+        );
+        #[cfg(feature = "extra-error")]
+        {
+            let extra = if later_wins {
+                serde_json::to_value(later.extra_message())
+            } else {
+                serde_json::to_value(former.extra_message())
+            };
+            detail.extra = Some(extra.with_expect("Bad Extra Message"));
+        }
+        merged
+    }
+
+    fn field_details(err: &impl RespError) -> Vec<ErrorDetail> {
+        match err.details() {
+            Some(details) => details,
+            // 服务端错误的信息不暴露给客户端
+            None if err.http_code().is_server_error() => Vec::new(),
+            None => vec![ErrorDetail::new(
+                "",
+                err.http_code().as_str().to_owned(),
+                err.resp_message().into_owned(),
+            )],
+        }
+    }
+}
+
+/// see [`SimpleRespError::merged`] for the merging rule, the status code does not depend on
+/// the order of fields, and the message of a server error never reaches the client
+impl MergeRejection for SimpleRespError {
+    fn merge(self, other: Self) -> Self {
+        Self::merged(&self, &other)
+    }
+}
+
+impl RespError for SimpleRespError {
+    fn log_message(&self) -> Cow<'_, str> {
+        let log_message = self.detail.as_ref().and_then(|d| d.log_message.as_ref());
+        Cow::Borrowed(log_message.unwrap_or(&self.message))
+    }
+
+    fn resp_message(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.message)
+    }
+
+    fn http_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn append_headers(&self, headers: &mut HeaderMap) {
+        let Some(detail) = &self.detail else {
+            return;
+        };
+        for (key, value) in &detail.headers {
+            headers.append(key, value.clone());
+        }
+    }
+
+    fn details(&self) -> Option<Vec<ErrorDetail>> {
+        self.detail.as_ref().and_then(|d| d.details.clone())
+    }
+
+    /// the extra message, default is the number of status code
+    #[cfg(feature = "extra-error")]
+    type ExtraMessage = serde_json::Value;
+
+    #[cfg(feature = "extra-error")]
+    fn extra_message(&self) -> Self::ExtraMessage {
+        self.detail
+            .as_ref()
+            .and_then(|d| d.extra.clone())
+            .unwrap_or_else(|| self.status.as_u16().into())
+    }
+}
+
+impl From<StatusCode> for SimpleRespError {
+    fn from(status: StatusCode) -> Self {
+        Self::new(status, status.canonical_reason().unwrap_or("Unknown Error"))
+    }
+}
+
+impl From<(StatusCode, &str)> for SimpleRespError {
+    fn from((status, message): (StatusCode, &str)) -> Self {
+        Self::new(status, message.to_owned())
+    }
+}
+
+impl From<(StatusCode, String)> for SimpleRespError {
+    fn from((status, message): (StatusCode, String)) -> Self {
+        Self::new(status, message)
+    }
+}
+
+impl From<Infallible> for SimpleRespError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+impl From<NotFoundError> for SimpleRespError {
+    fn from(err: NotFoundError) -> Self {
+        Self::new(err.http_code(), err.log_message().into_owned())
+    }
+}
+
+impl From<std::io::Error> for SimpleRespError {
+    fn from(err: std::io::Error) -> Self {
+        let status = match err.kind() {
+            std::io::ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            std::io::ErrorKind::PermissionDenied => StatusCode::FORBIDDEN,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        // 不向客户端暴露 IO 异常细节
+        Self::from(status).with_log_message(err.to_string())
+    }
+}
+
+/// the parse errors are caused by the bad request
+macro_rules! bad_request_from {
+    ($($err:ty),* $(,)?) => {
+        $(
+            impl From<$err> for SimpleRespError {
+                fn from(err: $err) -> Self {
+                    Self::new(StatusCode::BAD_REQUEST, err.to_string())
+                }
+            }
+        )*
+    };
+}
+
+bad_request_from!(
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::str::ParseBoolError,
+    std::char::ParseCharError,
+    std::str::Utf8Error,
+    std::string::FromUtf8Error,
+);
+
+/// the json error may come from serializing the output of handler, as `500 Internal Server Error`
+///
+/// map the json error of the request body into `400 Bad Request` explicitly if needed
+impl From<serde_json::Error> for SimpleRespError {
+    fn from(err: serde_json::Error) -> Self {
+        // 不向客户端暴露类型与字段细节
+        Self::from(StatusCode::INTERNAL_SERVER_ERROR).with_log_message(err.to_string())
+    }
+}
+
+/// the validation report as `422 Unprocessable Entity`, each invalid field as a detail,
+/// the field of nested struct and list is the path like `address.city` and `tags[0]`
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for SimpleRespError {
+    fn from(report: validator::ValidationErrors) -> Self {
+        fn collect(
+            prefix: &str,
+            report: &validator::ValidationErrors,
+            details: &mut Vec<ErrorDetail>,
+        ) {
+            use validator::ValidationErrorsKind;
+
+            for (field, kind) in report.errors() {
+                let path = if prefix.is_empty() {
+                    field.to_string()
+                } else {
+                    format!("{prefix}.{field}")
+                };
+                match kind {
+                    ValidationErrorsKind::Field(errors) => {
+                        details.extend(errors.iter().map(|err| {
+                            let message = err.message.clone().unwrap_or_else(|| err.code.clone());
+                            ErrorDetail::new(path.clone(), err.code.clone(), message)
+                        }))
+                    }
+                    ValidationErrorsKind::Struct(report) => collect(&path, report, details),
+                    ValidationErrorsKind::List(reports) => {
+                        for (idx, report) in reports {
+                            collect(&format!("{path}[{idx}]"), report, details)
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut details = Vec::new();
+        collect("", &report, &mut details);
+        // HashMap 无序，按字段排序保证输出稳定
+        details.sort_by(|a, b| a.field.cmp(&b.field));
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, report.to_string()).with_details(details)
+    }
+}
+
+/// the validation report as `422 Unprocessable Entity`, each invalid field as a detail
+#[cfg(feature = "garde")]
+impl From<garde::Report> for SimpleRespError {
+    fn from(report: garde::Report) -> Self {
+        let details = report
+            .iter()
+            .map(|(path, err)| ErrorDetail::new(path.to_string(), "invalid", err.to_string()))
+            .collect::<Vec<_>>();
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, report.to_string()).with_details(details)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::{HeaderMap, StatusCode};
+
+    use super::SimpleRespError;
+    use crate::{ErrorDetail, MergeRejection, NotFoundError, RespError};
+
+    #[test]
+    fn test_from() {
+        let err = SimpleRespError::from(StatusCode::CONFLICT);
+        assert_eq!(err.resp_message(), "Conflict");
+
+        let err = SimpleRespError::from((StatusCode::CONFLICT, "already exists"));
+        assert_eq!(err.http_code(), StatusCode::CONFLICT);
+        assert_eq!(err.resp_message(), "already exists");
+
+        let err = SimpleRespError::from(NotFoundError);
+        assert_eq!(err.http_code(), StatusCode::NOT_FOUND);
+
+        let err = SimpleRespError::from("a".parse::<i32>().unwrap_err());
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+
+        let err = SimpleRespError::from(std::io::Error::other("disk broken"));
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.resp_message(), "Internal Server Error");
+        assert_eq!(err.log_message(), "disk broken");
+
+        let json = serde_json::from_str::<u32>("\"a\"").unwrap_err();
+        let err = SimpleRespError::from(json);
+        assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.resp_message(), "Internal Server Error");
+        assert!(err.log_message().contains("invalid type"));
+    }
+
+    #[test]
+    fn test_headers() {
+        let err = SimpleRespError::from(StatusCode::TOO_MANY_REQUESTS)
+            .with_header("retry-after", "10")
+            .with_header("x-limit", "1")
+            .with_header("x-limit", "2");
+
+        let mut headers = HeaderMap::new();
+        err.append_headers(&mut headers);
+        assert_eq!(headers["retry-after"], "10");
+        assert_eq!(headers.get_all("x-limit").iter().count(), 2);
+
+        let err = SimpleRespError::from(StatusCode::TOO_MANY_REQUESTS);
+        assert!(err.clone().try_with_header("bad header", "1").is_err());
+        assert!(err.clone().try_with_header("x-limit", "\n").is_err());
+        let err = err.try_with_header("retry-after", "10").unwrap();
+        assert_eq!(err.detail.unwrap().headers["retry-after"], "10");
+    }
+
+    #[test]
+    fn test_merge() {
+        let id = SimpleRespError::new(StatusCode::BAD_REQUEST, "invalid id")
+            .with_detail(ErrorDetail::new("id", "parse", "invalid id"));
+        let page = SimpleRespError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid page")
+            .with_header("x-page", "1");
+
+        let err = id.clone().merge(page.clone());
+        assert_eq!(err.http_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.resp_message(), "Bad Request");
+        assert_eq!(err.log_message(), "invalid id; invalid page");
+        assert_eq!(
+            err.details().unwrap(),
+            [
+                ErrorDetail::new("id", "parse", "invalid id"),
+                ErrorDetail::new("", "422", "invalid page"),
+            ]
+        );
+        let mut headers = HeaderMap::new();
+        err.append_headers(&mut headers);
+        assert_eq!(headers["x-page"], "1");
+
+        // the server error wins wherever it is, its message never in the response
+        let internal = SimpleRespError::from(StatusCode::INTERNAL_SERVER_ERROR)
+            .with_log_message("missing path params");
+        for err in [
+            id.clone().merge(internal.clone()).merge(page.clone()),
+            internal.clone().merge(id.clone()).merge(page.clone()),
+        ] {
+            assert_eq!(err.http_code(), StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(err.resp_message(), "Internal Server Error");
+            assert_eq!(err.details().unwrap().len(), 2);
+            assert!(err.log_message().contains("missing path params"));
+        }
+    }
+}
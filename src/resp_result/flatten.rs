@@ -0,0 +1,388 @@
+use std::fmt::{self, Debug, Display};
+
+use serde::{
+    ser::{Impossible, SerializeMap, SerializeStruct},
+    Serialize, Serializer,
+};
+
+use crate::config::Layout;
+#[cfg(feature = "trace")]
+use tracing::{event, Level};
+
+/// the error of flattening the body
+pub(super) enum FlattenError<E> {
+    /// the body is not serialized into a map or struct, nothing has been written
+    NotMap,
+    /// the error of the underlying serializer
+    Inner(E),
+}
+
+impl<E: Display> Display for FlattenError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlattenError::NotMap => f.write_str("body is not a map"),
+            FlattenError::Inner(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: Display> Debug for FlattenError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<E: Display> std::error::Error for FlattenError<E> {}
+
+impl<E: serde::ser::Error> serde::ser::Error for FlattenError<E> {
+    fn custom<T: Display>(msg: T) -> Self {
+        FlattenError::Inner(E::custom(msg))
+    }
+}
+
+/// the body field colliding with the envelope field is skipped
+fn skip_collision(_key: &str) {
+    #[cfg(feature = "trace")]
+    event!(
+        Level::WARN,
+        flatten = "Collision",
+        field = _key,
+        "body field collides with the envelope field, skipped"
+    );
+}
+
+/// the [`Serializer`] that writes the fields of body into the map of envelope,
+/// only struct and map (maybe wrapped in newtype or `Some`) can be flattened
+pub(super) struct FlattenSerializer<'m, M> {
+    map: &'m mut M,
+    layout: &'m Layout,
+}
+
+impl<'m, M> FlattenSerializer<'m, M> {
+    pub(super) fn new(map: &'m mut M, layout: &'m Layout) -> Self {
+        Self { map, layout }
+    }
+}
+
+macro_rules! not_map {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
+                Err(FlattenError::NotMap)
+            }
+        )*
+    };
+}
+
+impl<'m, M: SerializeMap> Serializer for FlattenSerializer<'m, M> {
+    type Ok = ();
+    type Error = FlattenError<M::Error>;
+
+    type SerializeSeq = Impossible<(), Self::Error>;
+    type SerializeTuple = Impossible<(), Self::Error>;
+    type SerializeTupleStruct = Impossible<(), Self::Error>;
+    type SerializeTupleVariant = Impossible<(), Self::Error>;
+    type SerializeMap = FlattenMap<'m, M>;
+    type SerializeStruct = FlattenMap<'m, M>;
+    type SerializeStructVariant = Impossible<(), Self::Error>;
+
+    not_map! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(FlattenMap::new(self.map, self.layout))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(FlattenMap::new(self.map, self.layout))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(FlattenError::NotMap)
+    }
+}
+
+/// forward the entries of body to the map of envelope
+pub(super) struct FlattenMap<'m, M> {
+    map: &'m mut M,
+    layout: &'m Layout,
+    /// the key is skipped, skip its value too
+    skip_value: bool,
+}
+
+impl<'m, M> FlattenMap<'m, M> {
+    fn new(map: &'m mut M, layout: &'m Layout) -> Self {
+        Self {
+            map,
+            layout,
+            skip_value: false,
+        }
+    }
+}
+
+impl<M: SerializeMap> SerializeMap for FlattenMap<'_, M> {
+    type Ok = ();
+    type Error = FlattenError<M::Error>;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        if let Some(key) = key
+            .serialize(KeyCollision::new(self.layout))
+            .map_err(FlattenError::Inner)?
+        {
+            skip_collision(&key);
+            self.skip_value = true;
+            return Ok(());
+        }
+        self.map.serialize_key(key).map_err(FlattenError::Inner)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        if std::mem::take(&mut self.skip_value) {
+            return Ok(());
+        }
+        self.map.serialize_value(value).map_err(FlattenError::Inner)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<M: SerializeMap> SerializeStruct for FlattenMap<'_, M> {
+    type Ok = ();
+    type Error = FlattenError<M::Error>;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if self.layout.is_sibling_of_body(key) {
+            skip_collision(key);
+            return Ok(());
+        }
+        self.map
+            .serialize_entry(key, value)
+            .map_err(FlattenError::Inner)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// check whether the map key of body collides with the envelope field,
+/// get the colliding key if collides
+struct KeyCollision<'l, E> {
+    layout: &'l Layout,
+    _error: std::marker::PhantomData<E>,
+}
+
+impl<'l, E> KeyCollision<'l, E> {
+    fn new(layout: &'l Layout) -> Self {
+        Self {
+            layout,
+            _error: std::marker::PhantomData,
+        }
+    }
+}
+
+macro_rules! not_collide {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
+                Ok(None)
+            }
+        )*
+    };
+}
+
+impl<E: serde::ser::Error> Serializer for KeyCollision<'_, E> {
+    type Ok = Option<String>;
+    type Error = E;
+
+    type SerializeSeq = Impossible<Self::Ok, E>;
+    type SerializeTuple = Impossible<Self::Ok, E>;
+    type SerializeTupleStruct = Impossible<Self::Ok, E>;
+    type SerializeTupleVariant = Impossible<Self::Ok, E>;
+    type SerializeMap = Impossible<Self::Ok, E>;
+    type SerializeStruct = Impossible<Self::Ok, E>;
+    type SerializeStructVariant = Impossible<Self::Ok, E>;
+
+    not_collide! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, E> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, E> {
+        Ok(self.layout.is_sibling_of_body(v).then(|| v.to_owned()))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, E> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, E> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, E> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, E> {
+        Err(E::custom("flattened body key must be a string"))
+    }
+}
@@ -4,6 +4,7 @@ use std::fmt::Debug;
 
 use crate::resp_error::RespError;
 
+mod flatten;
 pub mod serde;
 pub mod to_response;
 mod try_macro;
@@ -1,13 +1,22 @@
-use serde::{ser::SerializeStruct, Serialize, Serializer};
+use serde::{ser::SerializeMap, Serialize, Serializer};
 #[cfg(feature = "trace")]
 use {
     std::any::type_name,
     tracing::{event, Level},
 };
 
-use crate::{get_config, resp_body::RespBody, resp_error::RespError};
+use crate::{
+    config::{FieldKind, InnerSerdeConfig, Layout, LayoutNode},
+    get_config,
+    resp_body::RespBody,
+    resp_context::RespContext,
+    resp_error::RespError,
+};
 
-use super::RespResult;
+use super::{
+    flatten::{FlattenError, FlattenSerializer},
+    RespResult,
+};
 
 pub trait RespSerialize {
     fn resp_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -31,23 +40,47 @@ where
     T: RespBody,
     E: RespError,
 {
-    #[cfg_attr(feature = "trace", tracing::instrument(skip_all))]
+    #[inline]
     fn resp_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let cfg = &get_config().serde;
-        let (ok_size, err_size) = cfg.get_field_size();
+        self.serialize_with_cfg(&get_config().serde, serializer)
+    }
+}
 
+impl<T, E> RespResult<T, E>
+where
+    T: RespBody,
+    E: RespError,
+{
+    #[cfg_attr(feature = "trace", tracing::instrument(skip_all))]
+    pub(crate) fn serialize_with_cfg<S>(
+        &self,
+        cfg: &InnerSerdeConfig,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
         #[cfg(feature = "trace")]
         event!(
             Level::TRACE,
-            serialize_field.Ok = ok_size,
-            serialize_field.Err = err_size
+            serialize_field.Ok = cfg.layout.size(true),
+            serialize_field.Err = cfg.layout.size(false)
         );
 
-        let resp = match self {
-            RespResult::Success(data) => {
+        #[cfg(feature = "context")]
+        let ctx = if cfg.meta_fields.is_empty() {
+            None
+        } else {
+            RespContext::current()
+        };
+        #[cfg(not(feature = "context"))]
+        let ctx = None::<RespContext>;
+
+        match self {
+            RespResult::Success(_data) => {
                 #[cfg(feature = "trace")]
                 event!(
                     Level::DEBUG,
@@ -56,48 +89,332 @@ where
                     "data.payload.type" =
                         type_name::<<T as crate::resp_body::LoadSerde>::SerdeData>()
                 );
-
-                let mut body = serializer.serialize_struct("RespResult", ok_size)?;
-                if let Some(ref signed_status) = cfg.signed_status {
-                    body.serialize_field(signed_status.field, &signed_status.ok)?;
-                }
-                if cfg.full_field {
-                    #[cfg(feature = "extra-error")]
-                    if let Some(ecl) = cfg.extra_code {
-                        body.serialize_field(ecl, &E::extra_message_default())?;
-                    }
-                    body.serialize_field(cfg.err_msg_name, &E::resp_message_default())?;
-                }
-
-                body.serialize_field(cfg.body_name, data.load_serde())?;
-
-                body.end()?
             }
-            RespResult::Err(err) => {
+            RespResult::Err(_err) => {
                 #[cfg(feature = "trace")]
                 event!(
                     Level::DEBUG,
                     entry = "Error",
                     "error.type" = type_name::<E>(),
-                    error = %err.log_message()
+                    error = %_err.log_message()
                 );
-                let mut body = serializer.serialize_struct("RespResult", err_size)?;
+            }
+        };
+
+        Envelope {
+            layout: &cfg.layout,
+            cfg,
+            resp: self,
+            ctx: ctx.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// serialize one level of the envelope [`Layout`]
+struct Envelope<'r, T, E> {
+    layout: &'r Layout,
+    cfg: &'r InnerSerdeConfig,
+    resp: &'r RespResult<T, E>,
+    ctx: Option<&'r RespContext>,
+}
 
-                if let Some(ref status_sign) = cfg.signed_status {
-                    body.serialize_field(status_sign.field, &status_sign.err)?;
+impl<'r, T, E> Envelope<'r, T, E> {
+    fn nested(&self, layout: &'r Layout) -> Self {
+        Self { layout, ..*self }
+    }
+}
+
+impl<T, E> Serialize for Envelope<'_, T, E>
+where
+    T: RespBody,
+    E: RespError,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let is_ok = matches!(self.resp, RespResult::Success(_));
+        // 展开响应体时字段数量未知
+        let flatten = is_ok && self.cfg.flatten_body && self.layout.contains_body();
+        let size = (!flatten).then(|| self.layout.size(is_ok));
+
+        let mut body = serializer.serialize_map(size)?;
+        for (key, node) in &self.layout.entries {
+            let leaf = match node {
+                LayoutNode::Nested(nested) => {
+                    if nested.size(is_ok) > 0 {
+                        body.serialize_entry(key, &self.nested(nested))?;
+                    }
+                    continue;
+                }
+                LayoutNode::Field(leaf) if (is_ok && leaf.on_ok) || (!is_ok && leaf.on_err) => leaf,
+                LayoutNode::Field(_) => continue,
+            };
+
+            match (leaf.kind, self.resp) {
+                (FieldKind::Sign, resp) => {
+                    let sign = self
+                        .cfg
+                        .signed_status
+                        .as_ref()
+                        .expect("status sign not set");
+                    match resp {
+                        RespResult::Success(_) => {
+                            body.serialize_entry(key, &sign.ok.value::<E>(None))?
+                        }
+                        RespResult::Err(err) => {
+                            body.serialize_entry(key, &sign.err.value(Some(err)))?
+                        }
+                    }
                 }
                 #[cfg(feature = "extra-error")]
-                if let Some(ecl) = cfg.extra_code {
-                    body.serialize_field(ecl, &err.extra_message())?;
+                (FieldKind::ExtraMessage, RespResult::Success(_)) => {
+                    body.serialize_entry(key, &E::extra_message_default())?
                 }
-                body.serialize_field(cfg.err_msg_name, &err.resp_message())?;
-
-                if cfg.full_field {
-                    body.serialize_field(cfg.body_name, &())?;
+                #[cfg(feature = "extra-error")]
+                (FieldKind::ExtraMessage, RespResult::Err(err)) => {
+                    body.serialize_entry(key, &err.extra_message())?
+                }
+                (FieldKind::ErrMessage, RespResult::Success(_)) => {
+                    body.serialize_entry(key, &E::resp_message_default())?
+                }
+                (FieldKind::ErrMessage, RespResult::Err(err)) => {
+                    body.serialize_entry(key, &err.resp_message())?
+                }
+                (FieldKind::Details, RespResult::Success(_)) => body.serialize_entry(key, &())?,
+                (FieldKind::Details, RespResult::Err(err)) => {
+                    body.serialize_entry(key, &err.details())?
+                }
+                (FieldKind::Body, RespResult::Success(data)) if flatten => {
+                    let serializer = FlattenSerializer::new(&mut body, self.layout);
+                    match data.load_serde().serialize(serializer) {
+                        Ok(()) => {}
+                        Err(FlattenError::NotMap) => {
+                            #[cfg(feature = "trace")]
+                            event!(
+                                Level::WARN,
+                                flatten = "Fallback",
+                                "body is not a map, serialize it into the body field"
+                            );
+                            body.serialize_entry(key, data.load_serde())?
+                        }
+                        Err(FlattenError::Inner(err)) => return Err(err),
+                    }
+                }
+                (FieldKind::Body, RespResult::Success(data)) => {
+                    body.serialize_entry(key, data.load_serde())?
+                }
+                (FieldKind::Body, RespResult::Err(_)) => body.serialize_entry(key, &())?,
+                (FieldKind::Meta(idx), _) => {
+                    let meta = &self.cfg.meta_fields[idx];
+                    body.serialize_entry(key, &meta.value(self.ctx))?
                 }
-                body.end()?
             }
-        };
-        Ok(resp)
+        }
+        body.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, collections::HashMap};
+
+    use serde::Serialize;
+
+    use crate::{
+        config::InnerSerdeConfig, ErrorDetail, RespError, RespResult, SerdeConfig, SignType,
+        StatusSign,
+    };
+
+    struct MockErr;
+
+    impl RespError for MockErr {
+        fn log_message(&self) -> Cow<'_, str> {
+            "Mock Error".into()
+        }
+        #[cfg(feature = "extra-error")]
+        type ExtraMessage = String;
+        #[cfg(feature = "extra-error")]
+        fn extra_message(&self) -> Self::ExtraMessage {
+            "Mock".into()
+        }
+        fn details(&self) -> Option<Vec<ErrorDetail>> {
+            Some(vec![ErrorDetail::new("name", "length", "name too long")])
+        }
+    }
+
+    struct FlattenConfig;
+
+    impl SerdeConfig for FlattenConfig {
+        fn flatten_body(&self) -> bool {
+            true
+        }
+    }
+
+    struct NestedConfig;
+
+    impl SerdeConfig for NestedConfig {
+        fn body_name(&self) -> Cow<'static, str> {
+            "data".into()
+        }
+
+        fn err_msg_name(&self) -> Cow<'static, str> {
+            "status.message".into()
+        }
+
+        fn signed_status(&self) -> Option<StatusSign> {
+            Some(StatusSign::new("status.ok", SignType::new_bool()))
+        }
+
+        #[cfg(feature = "extra-error")]
+        fn extra_message(&self) -> Option<Cow<'static, str>> {
+            Some("status.code".into())
+        }
+    }
+
+    struct DetailsConfig<const FIXED: bool>;
+
+    impl<const FIXED: bool> SerdeConfig for DetailsConfig<FIXED> {
+        fn fixed_field(&self) -> bool {
+            FIXED
+        }
+
+        fn details_name(&self) -> Option<Cow<'static, str>> {
+            Some("details".into())
+        }
+    }
+
+    struct CodeConfig;
+
+    impl SerdeConfig for CodeConfig {
+        fn signed_status(&self) -> Option<StatusSign> {
+            Some(StatusSign::new("code", SignType::new_business_code(0)))
+        }
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        id: u32,
+        name: &'static str,
+    }
+
+    fn to_json<T: crate::resp_body::RespBody>(
+        resp: &RespResult<T, MockErr>,
+        cfg: &InnerSerdeConfig,
+    ) -> Result<serde_json::Value, serde_json::Error> {
+        resp.serialize_with_cfg(cfg, serde_json::value::Serializer)
+    }
+
+    #[test]
+    fn test_flatten_body() {
+        let cfg = InnerSerdeConfig::into_inner(&FlattenConfig).unwrap();
+
+        let value = to_json(&RespResult::Success(User { id: 1, name: "foo" }), &cfg).unwrap();
+        assert_eq!(value["is-ok"], true);
+        assert_eq!(value["id"], 1);
+        assert_eq!(value["name"], "foo");
+        assert!(value.get("body").is_none());
+
+        // not a map, fallback to body field
+        let value = to_json(&RespResult::Success(12), &cfg).unwrap();
+        assert_eq!(value["body"], 12);
+
+        let value = to_json(&RespResult::<User, _>::Err(MockErr), &cfg).unwrap();
+        assert_eq!(value["error-message"], "Mock Error");
+        assert!(value.get("body").is_none());
+    }
+
+    #[test]
+    fn test_nested_field() {
+        let cfg = InnerSerdeConfig::into_inner(&NestedConfig).unwrap();
+
+        let value = to_json(&RespResult::Success(User { id: 1, name: "foo" }), &cfg).unwrap();
+        assert_eq!(value["status"]["ok"], true);
+        assert!(value["status"]["message"].is_null());
+        assert_eq!(value["data"]["id"], 1);
+
+        let value = to_json(&RespResult::<User, _>::Err(MockErr), &cfg).unwrap();
+        assert_eq!(value["status"]["ok"], false);
+        assert_eq!(value["status"]["message"], "Mock Error");
+        #[cfg(feature = "extra-error")]
+        assert_eq!(value["status"]["code"], "Mock");
+        assert!(value["data"].is_null());
+    }
+
+    #[test]
+    fn test_details() {
+        let cfg = InnerSerdeConfig::into_inner(&DetailsConfig::<true>).unwrap();
+
+        let value = to_json(&RespResult::Success(1), &cfg).unwrap();
+        assert!(value["details"].is_null());
+
+        let value = to_json(&RespResult::<i32, _>::Err(MockErr), &cfg).unwrap();
+        assert_eq!(
+            value["details"],
+            serde_json::json!([{"field": "name", "code": "length", "message": "name too long"}])
+        );
+
+        let cfg = InnerSerdeConfig::into_inner(&DetailsConfig::<false>).unwrap();
+        let value = to_json(&RespResult::Success(1), &cfg).unwrap();
+        assert!(value.get("details").is_none());
+    }
+
+    #[test]
+    fn test_error_code_sign() {
+        let cfg = InnerSerdeConfig::into_inner(&CodeConfig).unwrap();
+
+        let value = to_json(&RespResult::Success(1), &cfg).unwrap();
+        assert_eq!(value["code"], 0);
+
+        let value = to_json(&RespResult::<i32, _>::Err(MockErr), &cfg).unwrap();
+        assert_eq!(value["code"], 500);
+    }
+
+    #[test]
+    fn test_flatten_collision() {
+        let cfg = InnerSerdeConfig::into_inner(&FlattenConfig).unwrap();
+        let body = HashMap::from([("error-message", 1), ("name", 2)]);
+
+        // the colliding field is skipped
+        let json = to_json(&RespResult::Success(body), &cfg).unwrap();
+        assert_eq!(json["error-message"], serde_json::Value::Null);
+        assert_eq!(json["name"], 2);
+
+        #[derive(Serialize)]
+        struct Collide {
+            #[serde(rename = "is-ok")]
+            ok: bool,
+            id: u32,
+        }
+        let json = to_json(&RespResult::Success(Collide { ok: false, id: 1 }), &cfg).unwrap();
+        assert_eq!(json["is-ok"], true);
+        assert_eq!(json["id"], 1);
+
+        // the colliding key is not written twice
+        let mut buf = Vec::new();
+        RespResult::<_, MockErr>::Success(HashMap::from([("is-ok", 1)]))
+            .serialize_with_cfg(&cfg, &mut serde_json::Serializer::new(&mut buf))
+            .unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap().matches("is-ok").count(), 1);
+    }
+
+    #[test]
+    fn test_flatten_order() {
+        #[derive(Serialize)]
+        struct Unordered {
+            zeta: u8,
+            alpha: u8,
+        }
+
+        let cfg = InnerSerdeConfig::into_inner(&FlattenConfig).unwrap();
+        let mut buf = Vec::new();
+        RespResult::<_, MockErr>::Success(Unordered { zeta: 1, alpha: 2 })
+            .serialize_with_cfg(&cfg, &mut serde_json::Serializer::new(&mut buf))
+            .unwrap();
+
+        let json = String::from_utf8(buf).unwrap();
+        assert!(json.starts_with(r#"{"is-ok":true,"#));
+        assert!(json.ends_with(r#""zeta":1,"alpha":2}"#));
     }
 }
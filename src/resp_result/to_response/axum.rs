@@ -13,7 +13,9 @@ where
         use crate::expect_ext::ExpectExt;
 
         let respond = super::PrepareRespond::from_resp_result(&self);
-        let mut builder = axum::response::Response::builder().status(respond.status);
+        let mut builder = axum::response::Response::builder()
+            .status(respond.status)
+            .extension(crate::error_layer::Enveloped);
 
         builder
             .headers_mut()
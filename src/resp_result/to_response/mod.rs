@@ -116,6 +116,11 @@ impl PrepareRespond {
                 }
             }
         }
+        if let RespResult::Err(err) = resp {
+            #[cfg(feature = "trace")]
+            event!(Level::DEBUG, headers.error = "Append Error Headers");
+            err.append_headers(&mut self.headers);
+        }
         #[cfg(feature = "trace")]
         event!(Level::DEBUG, "Apply Header Effect");
         resp.headers_effect(&mut self.headers);
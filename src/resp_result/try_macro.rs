@@ -1,14 +1,121 @@
-/// similar to the [try](core::r#try) macro, but for [RespResult](crate::RespResult)
-/// - if the $expr is [RespResult::Success](crate::RespResult::Success), will make it be the value of this expr
-/// - if the $expr is [RespResult::Error](crate::RespResult::Success), will direct return with the error
+/// similar to the [try](core::r#try) macro, but for any [Fallible](crate::Fallible)
+/// - if the $expr is success, will make it be the value of this expr
+/// - if the $expr is failure, will direct return with the error converted by [`Into`]
+///
+/// the `rtry!(expr, err)` form will return with the provided `err` on failure,
+/// e.g. giving an error for `None`
+///
+/// both work in the function returning [RespResult](crate::RespResult) or [Result]
 #[macro_export]
 macro_rules! rtry {
-    { $exp:expr } => {
-        match $crate::IntoRespResult::into_rresult($exp.map_err(Into::into)){
-            $crate::RespResult::Success(value) => value,
-            err @ $crate::RespResult::Err(_) =>{
-                return err
-            },
+    { $exp:expr $(,)? } => {
+        match $crate::Fallible::to_result($exp) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(err) => {
+                return $crate::__private::FromFailure::from_failure(err)
+            }
         }
     };
+    { $exp:expr, $err:expr $(,)? } => {
+        match $crate::Fallible::to_result($exp) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(_) => {
+                return $crate::__private::FromFailure::from_failure($err)
+            }
+        }
+    };
+}
+
+/// return with the error directly, the error will be converted by [`Into`]
+///
+/// - `rbail!(err)`
+/// - `rbail!(status, "fmt", args...)`: return with a [SimpleRespError](crate::SimpleRespError),
+///   see [`resp_err!`](crate::resp_err)
+///
+/// works in the function returning [RespResult](crate::RespResult) or [Result]
+#[macro_export]
+macro_rules! rbail {
+    { $err:expr $(,)? } => {
+        return $crate::__private::FromFailure::from_failure($err)
+    };
+    { $status:expr, $($fmt:tt)+ } => {
+        return $crate::__private::FromFailure::from_failure($crate::resp_err!($status, $($fmt)+))
+    };
+}
+
+/// return with the error if the condition not hold
+///
+/// - `rensure!(cond, err)`
+/// - `rensure!(cond, status, "fmt", args...)`
+///
+/// works in the function returning [RespResult](crate::RespResult) or [Result]
+#[macro_export]
+macro_rules! rensure {
+    { $cond:expr, $($err:tt)+ } => {
+        if !$cond {
+            $crate::rbail!($($err)+);
+        }
+    };
+}
+
+/// create a [SimpleRespError](crate::SimpleRespError) with the status code and formatted message
+///
+/// ```rust
+/// use axum_resp_result::{resp_err, RespError, StatusCode};
+///
+/// let id = 12;
+/// let err = resp_err!(StatusCode::CONFLICT, "already exists: {}", id);
+/// assert_eq!(err.http_code(), StatusCode::CONFLICT);
+/// assert_eq!(err.log_message(), "already exists: 12");
+/// ```
+#[macro_export]
+macro_rules! resp_err {
+    { $status:expr, $($fmt:tt)+ } => {
+        $crate::SimpleRespError::new($status, ::std::format!($($fmt)+))
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use http::StatusCode;
+
+    use crate::{RespError, RespResult, SimpleRespError};
+
+    fn parse(v: &str) -> Result<i32, SimpleRespError> {
+        let v = rtry!(
+            v.parse::<i32>().ok(),
+            resp_err!(StatusCode::BAD_REQUEST, "bad `{v}`")
+        );
+        rensure!(v > 0, StatusCode::BAD_REQUEST, "`{}` not positive", v);
+        Ok(v)
+    }
+
+    fn find(v: Option<i32>) -> RespResult<i32, SimpleRespError> {
+        let v = rtry!(v, resp_err!(StatusCode::NOT_FOUND, "not found"));
+        if v == 0 {
+            rbail!(StatusCode::CONFLICT, "zero");
+        }
+        let v = rtry!(parse(&v.to_string()));
+        RespResult::Success(v)
+    }
+
+    #[test]
+    fn test_result() {
+        assert_eq!(parse("12"), Ok(12));
+        assert_eq!(parse("a").unwrap_err().message(), "bad `a`");
+        assert_eq!(parse("-1").unwrap_err().message(), "`-1` not positive");
+    }
+
+    #[test]
+    fn test_resp_result() {
+        let RespResult::Err(err) = find(None) else {
+            panic!("expect error")
+        };
+        assert_eq!(err.http_code(), StatusCode::NOT_FOUND);
+        let RespResult::Err(err) = find(Some(0)) else {
+            panic!("expect error")
+        };
+        assert_eq!(err.http_code(), StatusCode::CONFLICT);
+        assert!(matches!(find(Some(3)), RespResult::Success(3)));
+    }
 }